@@ -0,0 +1,204 @@
+use std::process::ExitCode;
+
+use advent_of_code_2024::{
+    bench::{self, Measurement, Stats},
+    days, input,
+    solution::{Part, Step},
+};
+
+const USAGE: &str = "Usage: cargo bench -- [options] [days...]
+
+Times parse, part 1 and part 2 of every day (or only the given days) on the
+real inputs, in $AOC_INPUT_DIR/dayNN.txt (default: ~/.cache/aoc/2024).
+
+  --iterations <n>   runs per step (default: 10)
+  --save <path>      writes the measurements as a JSON baseline
+  --baseline <path>  compares against a saved baseline, failing on regressions
+  --threshold <pct>  median slowdown counted as a regression (default: 10)";
+
+const STEPS: [Step; 3] = [Step::Parse, Step::Part(Part::One), Step::Part(Part::Two)];
+
+struct Options {
+    iterations: usize,
+    save: Option<String>,
+    baseline: Option<String>,
+    threshold: f64,
+    days: Vec<u8>,
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    match parse_options(&args).and_then(|options| run(&options)) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        iterations: 10,
+        save: None,
+        baseline: None,
+        threshold: 10.0,
+        days: vec![],
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("missing value for {}", name))
+        };
+
+        match arg.as_str() {
+            // passed by `cargo bench`
+            "--bench" => {}
+            "--iterations" => {
+                let iterations = value(arg)?;
+                options.iterations = iterations
+                    .parse()
+                    .ok()
+                    .filter(|iterations| *iterations > 0)
+                    .ok_or_else(|| format!("invalid iterations: {}", iterations))?;
+            }
+            "--save" => options.save = Some(value(arg)?),
+            "--baseline" => options.baseline = Some(value(arg)?),
+            "--threshold" => {
+                let threshold = value(arg)?;
+                options.threshold = threshold
+                    .parse()
+                    .map_err(|_| format!("invalid threshold: {}", threshold))?;
+            }
+            day => options
+                .days
+                .push(day.parse().map_err(|_| format!("invalid day: {}", day))?),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Whether no step regressed.
+fn run(options: &Options) -> Result<bool, String> {
+    let baseline = options
+        .baseline
+        .as_ref()
+        .map(|path| {
+            let json = std::fs::read_to_string(path)
+                .map_err(|error| format!("cannot read {}: {}", path, error))?;
+            bench::from_json(&json).map_err(|error| format!("invalid baseline at {}", error))
+        })
+        .transpose()?;
+
+    let mut measurements = vec![];
+
+    println!(
+        "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>8}",
+        "day", "step", "mean", "median", "stddev", "change"
+    );
+
+    for solution in days::registry() {
+        let day = solution.day();
+        if !options.days.is_empty() && !options.days.contains(&day) {
+            continue;
+        }
+
+        let input = match input::load(day) {
+            Ok(input) => input,
+            Err(error) => {
+                println!("{:>3}  skipped, {}", day, error);
+                continue;
+            }
+        };
+
+        for step in STEPS {
+            if matches!(step, Step::Part(part) if days::TOO_SLOW.contains(&(day, part))) {
+                continue;
+            }
+
+            let samples = (0..options.iterations)
+                .map(|_| solution.time(step, &input))
+                .collect::<Result<Vec<_>, _>>();
+
+            let samples = match samples {
+                Ok(samples) => samples,
+                Err(error) => {
+                    println!("{:>3}  skipped, invalid input at {}", day, error);
+                    break;
+                }
+            };
+
+            let measurement = Measurement {
+                day,
+                step,
+                stats: Stats::from_samples(&samples),
+            };
+            print_measurement(&measurement, baseline.as_deref());
+            measurements.push(measurement);
+        }
+    }
+
+    if let Some(path) = &options.save {
+        std::fs::write(path, bench::to_json(&measurements))
+            .map_err(|error| format!("cannot write {}: {}", path, error))?;
+        println!("\nbaseline saved to {}", path);
+    }
+
+    let Some(baseline) = baseline else {
+        return Ok(true);
+    };
+
+    let regressions = bench::compare(&baseline, &measurements)
+        .into_iter()
+        .filter(|comparison| comparison.is_regression(options.threshold / 100.0))
+        .map(|comparison| {
+            format!(
+                "day {} {}: {:+.1}%",
+                comparison.current.day,
+                comparison.current.step,
+                comparison.change().unwrap() * 100.0
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if regressions.is_empty() {
+        println!("\nno regression above {}%", options.threshold);
+        return Ok(true);
+    }
+
+    println!("\nregressions above {}%:", options.threshold);
+    for regression in regressions {
+        println!("  {}", regression);
+    }
+
+    Ok(false)
+}
+
+fn print_measurement(measurement: &Measurement, baseline: Option<&[Measurement]>) {
+    let change = baseline
+        .and_then(|baseline| {
+            bench::compare(baseline, std::slice::from_ref(measurement))[0].change()
+        })
+        .map_or(String::new(), |change| format!("{:+.1}%", change * 100.0));
+
+    println!(
+        "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>8}",
+        measurement.day,
+        measurement.step,
+        bench::format_duration(measurement.stats.mean),
+        bench::format_duration(measurement.stats.median),
+        bench::format_duration(measurement.stats.stddev),
+        change,
+    );
+}
@@ -0,0 +1,244 @@
+use std::{collections::BTreeMap, fmt::Display, path::PathBuf};
+
+use crate::{input, solution::Part, ParseError, Source};
+
+/// The answers to someone's own inputs, read from a TOML file with one table per day:
+///
+/// ```toml
+/// [day01]
+/// part1 = 1530215
+/// part2 = "26800609"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u8, Part), String>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail { .. } => "fail",
+            Verdict::Unknown => "unknown",
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    NoDirectory,
+    Unreadable(PathBuf, std::io::Error),
+    Invalid(PathBuf, ParseError),
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswersError::NoDirectory => {
+                write!(f, "no answers directory, set {} or HOME", input::DIR_VAR)
+            }
+            AnswersError::Unreadable(path, error) => {
+                write!(f, "cannot read {}: {}", path.display(), error)
+            }
+            AnswersError::Invalid(path, error) => {
+                write!(f, "invalid answers in {} at {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+/// Next to the inputs, in `answers.toml`.
+pub fn path() -> Option<PathBuf> {
+    Some(input::directory()?.join("answers.toml"))
+}
+
+impl Answers {
+    /// No file means no known answer yet, not an error.
+    pub fn load() -> Result<Self, AnswersError> {
+        let path = path().ok_or(AnswersError::NoDirectory)?;
+
+        match std::fs::read_to_string(&path) {
+            Ok(toml) => Self::from_toml(&toml).map_err(|error| AnswersError::Invalid(path, error)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(AnswersError::Unreadable(path, error)),
+        }
+    }
+
+    /// Only the subset of TOML the answers need: `[dayNN]` tables of `partN` keys, holding
+    /// integers or strings without escapes.
+    pub fn from_toml(toml: &str) -> Result<Self, ParseError> {
+        let source = Source::new(toml);
+        let mut answers = BTreeMap::new();
+        let mut day = None;
+
+        for line in toml.lines().map(strip_comment) {
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[') {
+                let number = source.strip_prefix(table, "day")?;
+                let number = number
+                    .strip_suffix(']')
+                    .ok_or_else(|| source.error(number, "`]`"))?;
+                day = Some(
+                    source
+                        .number::<u8>(number)
+                        .ok()
+                        .filter(|day| (1..=25).contains(day))
+                        .ok_or_else(|| source.error(number, "a day between 1 and 25"))?,
+                );
+                continue;
+            }
+
+            let day = day.ok_or_else(|| source.error(line, "a `[dayNN]` table"))?;
+
+            let (key, value) = source.split_once(line, "=")?;
+            let part = match key.trim_end() {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                _ => return Err(source.error(key, "`part1` or `part2`")),
+            };
+
+            let value = value.trim_start();
+            let answer = match value.strip_prefix('"') {
+                Some(string) => string
+                    .strip_suffix('"')
+                    .filter(|string| !string.contains('"'))
+                    .ok_or_else(|| source.error(value, "a string"))?,
+                None => {
+                    source.number::<i64>(value)?;
+                    value
+                }
+            };
+
+            if answers.insert((day, part), answer.to_string()).is_some() {
+                return Err(source.error(key, "a part not answered yet"));
+            }
+        }
+
+        Ok(Self(answers))
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Verdict {
+        match self.get(day, part) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+/// The line without its comment and the whitespace around it. Strings hold no escapes, so a `#`
+/// is in one when an odd number of quotes comes before it.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let end = line
+        .char_indices()
+        .find(|(_, c)| {
+            if *c == '"' {
+                in_string = !in_string;
+            }
+            *c == '#' && !in_string
+        })
+        .map_or(line.len(), |(i, _)| i);
+
+    line[..end].trim()
+}
+
+/// The recorded answer to a part of the real input of a day in a test, returning from the test
+/// when there's none, so it goes before solving the input.
+#[cfg(test)]
+macro_rules! real_answer {
+    ($day:expr, $part:expr) => {
+        match $crate::answers::Answers::load() {
+            Ok(answers) => match answers.get($day, $part) {
+                Some(answer) => answer.to_string(),
+                None => {
+                    eprintln!("skipping, no answer to day {} {:?}", $day, $part);
+                    return;
+                }
+            },
+            Err(error) => {
+                eprintln!("skipping, {}", error);
+                return;
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+pub(crate) use real_answer;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "# my inputs
+[day01]
+part1 = 11 # from the example
+part2 = \"31\"
+
+[day17] # the computer
+part1 = \"4,6,3,5,6,3,5,2,1,0\"
+
+[day25]
+part1 = \"#1\" # not a number";
+
+    #[test]
+    fn test_from_toml() {
+        let answers = Answers::from_toml(ANSWERS).unwrap();
+
+        assert_eq!(answers.get(1, Part::One), Some("11"));
+        assert_eq!(answers.get(1, Part::Two), Some("31"));
+        assert_eq!(answers.get(17, Part::One), Some("4,6,3,5,6,3,5,2,1,0"));
+        assert_eq!(answers.get(17, Part::Two), None);
+        assert_eq!(answers.get(25, Part::One), Some("#1"));
+        assert_eq!(Answers::from_toml(""), Ok(Answers::default()));
+    }
+
+    #[test]
+    fn test_from_toml_errors() {
+        let error = Answers::from_toml("part1 = 1").unwrap_err();
+        assert_eq!(error.expected, "a `[dayNN]` table");
+
+        let error = Answers::from_toml("[day26]").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+
+        let error = Answers::from_toml("[day01]\npart3 = 1").unwrap_err();
+        assert_eq!(error.found, Some("part3".to_string()));
+
+        let error = Answers::from_toml("[day01]\npart1 = 1\npart1 = 2").unwrap_err();
+        assert_eq!(error.line, 3);
+
+        let error = Answers::from_toml("[day01]\npart1 = \"1 # 2").unwrap_err();
+        assert_eq!(error.expected, "a string");
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::from_toml(ANSWERS).unwrap();
+
+        assert_eq!(answers.check(1, Part::One, "11"), Verdict::Pass);
+        assert_eq!(
+            answers.check(1, Part::Two, "30"),
+            Verdict::Fail {
+                expected: "31".to_string()
+            }
+        );
+        assert_eq!(answers.check(2, Part::One, "2"), Verdict::Unknown);
+    }
+}
@@ -0,0 +1,224 @@
+use std::time::Duration;
+
+use crate::{solution::Step, ParseError, Source};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "at least one sample is needed");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[sorted.len() / 2 - 1] + sorted[sorted.len() / 2]) / 2
+        } else {
+            sorted[sorted.len() / 2]
+        };
+
+        let mean = samples.iter().sum::<Duration>() / samples.len() as u32;
+        let variance = samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / samples.len() as f64;
+
+        Self {
+            mean,
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    pub day: u8,
+    pub step: Step,
+    pub stats: Stats,
+}
+
+pub struct Comparison<'a> {
+    pub current: &'a Measurement,
+    pub baseline: Option<&'a Measurement>,
+}
+
+impl Comparison<'_> {
+    /// Relative change of the median, `0.1` being 10% slower than the baseline.
+    pub fn change(&self) -> Option<f64> {
+        let baseline = self.baseline?.stats.median.as_secs_f64();
+        let current = self.current.stats.median.as_secs_f64();
+
+        (baseline > 0.0).then(|| current / baseline - 1.0)
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+}
+
+pub fn compare<'a>(baseline: &'a [Measurement], current: &'a [Measurement]) -> Vec<Comparison<'a>> {
+    current
+        .iter()
+        .map(|current| Comparison {
+            current,
+            baseline: baseline
+                .iter()
+                .find(|b| b.day == current.day && b.step == current.step),
+        })
+        .collect()
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+
+    match nanos {
+        n if n < 1e3 => format!("{:.0}ns", n),
+        n if n < 1e6 => format!("{:.1}µs", n / 1e3),
+        n if n < 1e9 => format!("{:.1}ms", n / 1e6),
+        n => format!("{:.2}s", n / 1e9),
+    }
+}
+
+/// One measurement per line, so that baselines diff nicely.
+pub fn to_json(measurements: &[Measurement]) -> String {
+    let lines = measurements
+        .iter()
+        .map(|m| {
+            format!(
+                r#"  {{"day": {}, "step": "{}", "mean_ns": {}, "median_ns": {}, "stddev_ns": {}}}"#,
+                m.day,
+                m.step,
+                m.stats.mean.as_nanos(),
+                m.stats.median.as_nanos(),
+                m.stats.stddev.as_nanos(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!("[\n{}\n]\n", lines.join(",\n"))
+}
+
+/// Reads back what [`to_json`] writes, not arbitrary JSON.
+pub fn from_json(json: &str) -> Result<Vec<Measurement>, ParseError> {
+    let source = Source::new(json);
+    let mut lines = json.lines().map(str::trim).filter(|line| !line.is_empty());
+
+    let open = source.next_line(&mut lines, "`[`")?;
+    if open != "[" {
+        return Err(source.error(open, "`[`"));
+    }
+
+    let mut measurements = vec![];
+
+    for line in lines {
+        if line == "]" {
+            return Ok(measurements);
+        }
+
+        let object = source.strip_prefix(line, "{")?;
+        let object = object.strip_suffix(',').unwrap_or(object);
+        let object = object
+            .strip_suffix('}')
+            .ok_or_else(|| source.error(object, "`}`"))?;
+
+        let mut fields = object.split(", ");
+        let mut field = |name: &str| {
+            let field = source.next_line(&mut fields, &format!("`\"{}\"`", name))?;
+            source.strip_prefix(field, &format!("\"{}\": ", name))
+        };
+
+        let day = source.number::<u8>(field("day")?)?;
+        let step = field("step")?;
+        let step = step
+            .trim_matches('"')
+            .parse::<Step>()
+            .map_err(|_| source.error(step, "`\"parse\"`, `\"part1\"` or `\"part2\"`"))?;
+        let mut nanos = |name: &str| -> Result<Duration, ParseError> {
+            Ok(Duration::from_nanos(source.number::<u64>(field(name)?)?))
+        };
+
+        measurements.push(Measurement {
+            day,
+            step,
+            stats: Stats {
+                mean: nanos("mean_ns")?,
+                median: nanos("median_ns")?,
+                stddev: nanos("stddev_ns")?,
+            },
+        });
+    }
+
+    Err(source.end("`]`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Part;
+
+    fn measurement(day: u8, step: Step, median_ms: u64) -> Measurement {
+        Measurement {
+            day,
+            step,
+            stats: Stats {
+                mean: Duration::from_millis(median_ms),
+                median: Duration::from_millis(median_ms),
+                stddev: Duration::ZERO,
+            },
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&[2, 4, 4, 4, 5, 5, 7, 9].map(Duration::from_millis));
+
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert_eq!(stats.median, Duration::from_millis(9) / 2);
+        assert_eq!(stats.stddev, Duration::from_millis(2));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let measurements = vec![
+            measurement(1, Step::Parse, 1),
+            measurement(24, Step::Part(Part::Two), 250),
+        ];
+
+        assert_eq!(from_json(&to_json(&measurements)), Ok(measurements));
+        assert_eq!(from_json("[\n]\n"), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_json_errors() {
+        assert_eq!(
+            from_json("[\n  {\"day\": 1, \"step\": \"part3\"}\n]")
+                .unwrap_err()
+                .found,
+            Some("\"part3\"".to_string())
+        );
+        assert_eq!(from_json("[\n").unwrap_err().expected, "`]`".to_string());
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = vec![measurement(1, Step::Parse, 100)];
+        let current = vec![
+            measurement(1, Step::Parse, 120),
+            measurement(2, Step::Parse, 100),
+        ];
+
+        let comparisons = compare(&baseline, &current);
+
+        assert!(comparisons[0].change().unwrap() > 0.19);
+        assert!(comparisons[0].is_regression(0.1));
+        assert!(!comparisons[0].is_regression(0.25));
+        assert_eq!(comparisons[1].change(), None);
+    }
+}
@@ -0,0 +1,326 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+/// An unsigned integer as wide as it needs, for registers that don't fit in a `u128` or the
+/// values of the wires of a circuit.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    /// Least significant first, without trailing zeros so that each value has a single form.
+    limbs: Vec<u64>,
+}
+
+impl BigUint {
+    pub(crate) fn from_limbs(mut limbs: Vec<u64>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+
+        Self { limbs }
+    }
+
+    /// The least significant bit first.
+    pub fn from_bits(bits: impl IntoIterator<Item = bool>) -> Self {
+        let mut limbs = vec![];
+        for (i, bit) in bits.into_iter().enumerate() {
+            if i % 64 == 0 {
+                limbs.push(0);
+            }
+            if bit {
+                *limbs.last_mut().expect("a limb for each bit") |= 1 << (i % 64);
+            }
+        }
+
+        Self::from_limbs(limbs)
+    }
+
+    pub fn bit(&self, i: usize) -> bool {
+        self.limbs
+            .get(i / 64)
+            .is_some_and(|limb| limb >> (i % 64) & 1 == 1)
+    }
+
+    /// The number of bits needed to write it, 0 for 0.
+    pub fn bit_length(&self) -> usize {
+        self.limbs.last().map_or(0, |last| {
+            64 * (self.limbs.len() - 1) + (64 - last.leading_zeros()) as usize
+        })
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// `None` if it doesn't fit.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [limb] => Some(limb),
+            _ => None,
+        }
+    }
+
+    /// The value modulo `2^width`.
+    pub fn truncate(&self, width: usize) -> Self {
+        let mut limbs = self.limbs.clone();
+        limbs.truncate(width.div_ceil(64));
+        if let (Some(last), 1..) = (limbs.get_mut(width / 64), width % 64) {
+            *last &= (1 << (width % 64)) - 1;
+        }
+
+        Self::from_limbs(limbs)
+    }
+
+    fn zip_limbs(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        let len = self.limbs.len().max(other.limbs.len());
+        let limb = |limbs: &[u64], i| limbs.get(i).copied().unwrap_or(0);
+
+        Self::from_limbs(
+            (0..len)
+                .map(|i| f(limb(&self.limbs, i), limb(&other.limbs, i)))
+                .collect(),
+        )
+    }
+
+    pub fn and(&self, other: &Self) -> Self {
+        self.zip_limbs(other, |a, b| a & b)
+    }
+
+    pub fn or(&self, other: &Self) -> Self {
+        self.zip_limbs(other, |a, b| a | b)
+    }
+
+    pub fn xor(&self, other: &Self) -> Self {
+        self.zip_limbs(other, |a, b| a ^ b)
+    }
+
+    /// Divides by `2^bits`, which gives 0 once `bits` is past the most significant bit.
+    pub fn shr(&self, bits: u64) -> Self {
+        let Some(kept) = self
+            .limbs
+            .get(usize::try_from(bits / 64).unwrap_or(usize::MAX)..)
+        else {
+            return Self::default();
+        };
+        let shift = bits % 64;
+
+        Self::from_limbs(
+            (0..kept.len())
+                .map(|i| match (shift, kept.get(i + 1)) {
+                    (0, _) => kept[i],
+                    (_, Some(next)) => kept[i] >> shift | next << (64 - shift),
+                    (_, None) => kept[i] >> shift,
+                })
+                .collect(),
+        )
+    }
+
+    /// Multiplies by `2^bits`.
+    pub fn shl(&self, bits: u64) -> Self {
+        if self.limbs.is_empty() {
+            return Self::default();
+        }
+
+        let shift = bits % 64;
+        let mut limbs = vec![0; (bits / 64) as usize];
+        let mut carry = 0;
+        for limb in &self.limbs {
+            limbs.push(limb << shift | carry);
+            carry = if shift == 0 { 0 } else { limb >> (64 - shift) };
+        }
+        limbs.push(carry);
+
+        Self::from_limbs(limbs)
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        let len = self.limbs.len().max(other.limbs.len());
+        let limb = |limbs: &[u64], i| limbs.get(i).copied().unwrap_or(0);
+
+        let mut carry = false;
+        let mut limbs = (0..len)
+            .map(|i| {
+                let (sum, overflow) = limb(&self.limbs, i).overflowing_add(limb(&other.limbs, i));
+                let (sum, carried) = sum.overflowing_add(carry as u64);
+                carry = overflow || carried;
+                sum
+            })
+            .collect::<Vec<_>>();
+        limbs.push(carry as u64);
+
+        Self::from_limbs(limbs)
+    }
+
+    /// `self - other` modulo `2^width`, as the two's complement hardware gives it.
+    pub fn wrapping_sub(&self, other: &Self, width: usize) -> Self {
+        let mask = Self::from_bits((0..width).map(|_| true));
+        let negated = other.truncate(width).xor(&mask).add(&Self::from(1));
+
+        self.add(&negated).truncate(width)
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut limbs = vec![0; self.limbs.len() + other.limbs.len()];
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, b) in other.limbs.iter().enumerate() {
+                let product = *a as u128 * *b as u128 + limbs[i + j] as u128 + carry;
+                limbs[i + j] = product as u64;
+                carry = product >> 64;
+            }
+            limbs[i + other.limbs.len()] = carry as u64;
+        }
+
+        Self::from_limbs(limbs)
+    }
+
+    /// `self * factor + term`.
+    fn mul_add(&self, factor: u64, term: u64) -> Self {
+        let mut carry = term as u128;
+        let mut limbs = self
+            .limbs
+            .iter()
+            .map(|limb| {
+                let value = *limb as u128 * factor as u128 + carry;
+                carry = value >> 64;
+                value as u64
+            })
+            .collect::<Vec<_>>();
+        limbs.push(carry as u64);
+
+        Self::from_limbs(limbs)
+    }
+
+    /// The quotient and the remainder.
+    fn div_rem(&self, divisor: u64) -> (Self, u64) {
+        let mut remainder = 0u128;
+        let mut limbs = self
+            .limbs
+            .iter()
+            .rev()
+            .map(|limb| {
+                let value = remainder << 64 | *limb as u128;
+                remainder = value % divisor as u128;
+                (value / divisor as u128) as u64
+            })
+            .collect::<Vec<_>>();
+        limbs.reverse();
+
+        (Self::from_limbs(limbs), remainder as u64)
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self::from_limbs(vec![value])
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// the largest power of 10 that fits in a limb
+const DECIMAL_LIMB: u64 = 10_000_000_000_000_000_000;
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut chunks = vec![];
+        let mut rest = self.clone();
+        while !rest.is_zero() {
+            let (quotient, chunk) = rest.div_rem(DECIMAL_LIMB);
+            chunks.push(chunk);
+            rest = quotient;
+        }
+
+        let Some(first) = chunks.pop() else {
+            return f.pad_integral(true, "", "0");
+        };
+        let digits = chunks
+            .iter()
+            .rev()
+            .fold(first.to_string(), |digits, chunk| {
+                format!("{}{:019}", digits, chunk)
+            });
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigUintError;
+
+impl Display for ParseBigUintError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid digit in an unsigned integer")
+    }
+}
+
+impl std::error::Error for ParseBigUintError {}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseBigUintError);
+        }
+
+        s.chars().try_fold(Self::default(), |value, c| {
+            let digit = c.to_digit(10).ok_or(ParseBigUintError)?;
+            Ok(value.mul_add(10, digit as u64))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_big_uint() {
+        let two_to_the = |power| BigUint::from(1).shl(power);
+        let big = "1606938044258990275541962092341162602522202993782792835301376";
+
+        assert_eq!(big.parse::<BigUint>(), Ok(two_to_the(200)));
+        assert_eq!(two_to_the(200).to_string(), big);
+        assert_eq!(
+            "18446744073709551616".parse::<BigUint>(),
+            Ok(two_to_the(64))
+        );
+        assert_eq!(format!("{:>5}", BigUint::default()), "    0");
+        assert_eq!("12a".parse::<BigUint>(), Err(ParseBigUintError));
+
+        assert_eq!(two_to_the(200).bit_length(), 201);
+        assert_eq!(two_to_the(200).shr(197), BigUint::from(8));
+        assert!(two_to_the(200).shr(201).is_zero());
+        assert_eq!(two_to_the(130).xor(&two_to_the(130)), BigUint::default());
+        assert!(two_to_the(130) > BigUint::from(u64::MAX));
+        assert_eq!(two_to_the(130).to_u64(), None);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let big = BigUint::from_bits((0..130).map(|bit| bit == 129));
+        assert_eq!(big.to_string(), "680564733841876926926749214863536422912");
+        assert_eq!(big.bit_length(), 130);
+        assert_eq!(big.truncate(129), BigUint::default());
+        assert_eq!(BigUint::from(36).truncate(70), BigUint::from(36));
+        assert_eq!(
+            big.add(&big).mul(&BigUint::from(3)).to_string(),
+            "4083388403051261561560495289181218537472"
+        );
+        assert_eq!(
+            BigUint::from(2).wrapping_sub(&BigUint::from(3), 4),
+            BigUint::from(15)
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use std::{io::Read, path::Path, process::ExitCode};
+
+use advent_of_code_2024::{
+    answers::{self, Answers, Verdict},
+    big::BigUint,
+    days::{
+        self,
+        day17::{self, Day17, Limits, Program, Trace, TraceFormat},
+        day24::Day24,
+    },
+    input, scaffold,
+    solution::{Part, RunError, Solution},
+};
+
+const USAGE: &str = "Usage: aoc <day> <part> [input|-] [args...]
+       aoc verify [days...]
+       aoc new <day> [--example <path>]
+       aoc debug [input]
+       aoc trace <csv|jsonl> [input] [--a <value>]
+       aoc dot [input] [--highlight <wires>] [--suspects]
+       aoc export <verilog|blif> [input] [--repair]
+
+Reads the puzzle input from the given path, from stdin when the path is `-`,
+or from $AOC_INPUT_DIR/dayNN.txt (default: ~/.cache/aoc/2024) when it is
+omitted. Some days take extra arguments, defaulting to the values used
+by the real puzzle:
+
+  day 14: <width> <height>                  (default: 101 103)
+  day 18: <width> <height> <bytes_fallen>   (default: 71 71 1024)
+  day 20: <picoseconds_to_save>             (default: 100)
+  day 24: part 2 only, <swapped> <sum|and>  (default: 4 sum)
+
+`verify` runs every day (or only the given days) on its cached input, and
+checks the answers against the answers.toml file next to the inputs:
+
+  [day01]
+  part1 = 1530215
+  part2 = \"26800609\"
+
+Parts too slow to run by default are only verified when their day is given.
+
+`new` creates src/days/dayNN/mod.rs from the day template, registers it and
+creates an empty input file. The example in the tests can be seeded from a
+file. An existing day is never overwritten.
+
+`debug` steps through a day 17 program, reading debugger commands from stdin.
+
+`trace` runs a day 17 program, optionally with another value in register A,
+and prints every step: the instruction pointer, the instruction, the
+registers before and after it and its output.
+
+`dot` prints a day 24 circuit as a Graphviz graph, in red the given
+comma-separated wires and, with `--suspects`, the wires that the adder
+check finds swapped.
+
+`export` prints a day 24 circuit as structural Verilog or BLIF, with
+`--repair` after swapping the outputs that the adder check finds swapped.";
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let result = match args.first().map(String::as_str) {
+        Some("verify") => verify(&args[1..]),
+        Some("new") => new_day(&args[1..]).map(|_| true),
+        Some("debug") => debug(&args[1..]).map(|_| true),
+        Some("trace") => trace(&args[1..]).map(|_| true),
+        Some("dot") => dot(&args[1..]).map(|_| true),
+        Some("export") => export(&args[1..]).map(|_| true),
+        _ => run(&args).map(|answer| {
+            println!("{}", answer);
+            true
+        }),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(Error::Usage(error)) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            ExitCode::FAILURE
+        }
+        Err(Error::Run(error)) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+enum Error {
+    Usage(String),
+    Run(String),
+}
+
+impl From<String> for Error {
+    fn from(error: String) -> Self {
+        Error::Usage(error)
+    }
+}
+
+fn run(args: &[String]) -> Result<String, Error> {
+    let [day, part, rest @ ..] = args else {
+        return Err(Error::Usage("missing day and part".to_string()));
+    };
+
+    let day = day
+        .parse::<u8>()
+        .map_err(|_| format!("invalid day: {}", day))?;
+    let part = part.parse::<Part>()?;
+    let (input_path, extra) = match rest {
+        [] => (None, rest),
+        [path, extra @ ..] => (Some(path.as_str()), extra),
+    };
+
+    let solution = days::registry()
+        .into_iter()
+        .find(|solution| solution.day() == day)
+        .ok_or_else(|| format!("day must be between 1 and 25, got {}", day))?;
+
+    let input = read_input(day, input_path).map_err(Error::Run)?;
+
+    solution
+        .run(part, &input, extra)
+        .map_err(|error| match error {
+            RunError::Args(error) => Error::Usage(error),
+            RunError::Parse(_) => Error::Run(error.to_string()),
+        })
+}
+
+/// Whether every part that ran matches its known answer.
+fn verify(args: &[String]) -> Result<bool, Error> {
+    let only = args
+        .iter()
+        .map(|day| {
+            day.parse::<u8>()
+                .map_err(|_| format!("invalid day: {}", day))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let answers = Answers::load().map_err(|error| Error::Run(error.to_string()))?;
+    let (mut passed, mut failed, mut unknown) = (0, 0, 0);
+
+    println!("{:>3}  {:<4}  {:<7}  answer", "day", "part", "result");
+
+    for solution in days::registry() {
+        let day = solution.day();
+        if !only.is_empty() && !only.contains(&day) {
+            continue;
+        }
+
+        let input = match input::load(day) {
+            Ok(input) => input,
+            Err(error) => {
+                println!("{:>3}  skipped, {}", day, error);
+                continue;
+            }
+        };
+
+        for (part, name) in [(Part::One, "1"), (Part::Two, "2")] {
+            if only.is_empty() && days::TOO_SLOW.contains(&(day, part)) {
+                println!("{:>3}  {:<4}  skipped, too slow", day, name);
+                continue;
+            }
+
+            let answer = match solution.run(part, &input, &[]) {
+                Ok(answer) => answer,
+                Err(error) => {
+                    println!("{:>3}  {:<4}  {:<7}  {}", day, name, "error", error);
+                    failed += 1;
+                    continue;
+                }
+            };
+
+            let verdict = answers.check(day, part, &answer);
+            match &verdict {
+                Verdict::Pass => passed += 1,
+                Verdict::Fail { .. } => failed += 1,
+                Verdict::Unknown => unknown += 1,
+            }
+
+            let answer = match verdict {
+                Verdict::Fail { ref expected } => format!("{} (expected {})", answer, expected),
+                _ => answer,
+            };
+            println!("{:>3}  {:<4}  {:<7}  {}", day, name, verdict, answer);
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} unknown",
+        passed, failed, unknown
+    );
+    if unknown > 0 {
+        if let Some(path) = answers::path() {
+            println!("unknown answers can be recorded in {}", path.display());
+        }
+    }
+
+    Ok(failed == 0)
+}
+
+fn new_day(args: &[String]) -> Result<(), Error> {
+    let (day, example) = match args {
+        [day] => (day, None),
+        [day, flag, path] if flag == "--example" => (day, Some(path)),
+        _ => {
+            return Err(Error::Usage(
+                "expected a day and an optional example".to_string(),
+            ))
+        }
+    };
+
+    let day = day
+        .parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| format!("day must be between 1 and 25, got {}", day))?;
+
+    let example = example
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .map_err(|error| Error::Run(format!("cannot read {}: {}", path, error)))
+        })
+        .transpose()?;
+
+    let generated = scaffold::generate(
+        Path::new(env!("CARGO_MANIFEST_DIR")),
+        day,
+        example.as_deref(),
+    )
+    .map_err(|error| Error::Run(error.to_string()))?;
+
+    println!("created {}", generated.module.display());
+    if let Some(input) = generated.input {
+        println!("created {}", input.display());
+    }
+
+    Ok(())
+}
+
+fn debug(args: &[String]) -> Result<(), Error> {
+    let path = match args {
+        [] => None,
+        [path] if path != "-" => Some(path.as_str()),
+        _ => return Err(Error::Usage("expected an optional input path".to_string())),
+    };
+
+    let input = read_input(17, path).map_err(Error::Run)?;
+    let program = Day17::parse(&input).map_err(|error| Error::Run(error.to_string()))?;
+
+    day17::repl(program, std::io::stdin().lock(), std::io::stdout())
+        .map_err(|error| Error::Run(error.to_string()))
+}
+
+fn trace(args: &[String]) -> Result<(), Error> {
+    let (format, path, a) = match args {
+        [format] => (format, None, None),
+        [format, flag, a] if flag == "--a" => (format, None, Some(a)),
+        [format, path] => (format, Some(path.as_str()), None),
+        [format, path, flag, a] if flag == "--a" => (format, Some(path.as_str()), Some(a)),
+        _ => {
+            return Err(Error::Usage(
+                "expected a format, an optional input path and an optional value of A".to_string(),
+            ))
+        }
+    };
+    let format = format.parse::<TraceFormat>()?;
+
+    let input = read_input(17, path).map_err(Error::Run)?;
+    let mut program =
+        Program::<BigUint>::from_input(&input).map_err(|error| Error::Run(error.to_string()))?;
+    if let Some(a) = a {
+        let a = a
+            .parse::<BigUint>()
+            .map_err(|_| format!("invalid value of A: {}", a))?;
+        program = program.with_a(a);
+    }
+
+    let mut trace = Trace::new();
+    let result = program.run_traced(&Limits::default().with_loop_detection(), &mut trace);
+    trace
+        .write(format, std::io::stdout().lock())
+        .map_err(|error| Error::Run(error.to_string()))?;
+
+    result
+        .map(|_| ())
+        .map_err(|error| Error::Run(error.to_string()))
+}
+
+fn dot(args: &[String]) -> Result<(), Error> {
+    let mut path = None;
+    let mut highlighted = vec![];
+    let mut suspects = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--highlight" => {
+                let wires = args
+                    .next()
+                    .ok_or_else(|| "expected wires after --highlight".to_string())?;
+                highlighted.extend(wires.split(',').map(str::to_string));
+            }
+            "--suspects" => suspects = true,
+            _ if path.is_none() => path = Some(arg.as_str()),
+            _ => return Err(Error::Usage(format!("unexpected argument: {}", arg))),
+        }
+    }
+
+    let input = read_input(24, path).map_err(Error::Run)?;
+    let device = Day24::parse(&input).map_err(|error| Error::Run(error.to_string()))?;
+    if suspects {
+        let report = device
+            .check_adder()
+            .map_err(|error| Error::Run(error.to_string()))?;
+        for fault in &report.faults {
+            eprintln!("{}", fault);
+        }
+        highlighted.extend(report.swapped_wires().into_iter().map(str::to_string));
+    }
+
+    print!(
+        "{}",
+        device
+            .dot()
+            .highlight(highlighted.iter().map(String::as_str))
+    );
+    Ok(())
+}
+
+fn export(args: &[String]) -> Result<(), Error> {
+    let mut args = args.iter();
+    let format = args
+        .next()
+        .ok_or_else(|| "expected verilog or blif".to_string())?;
+    if format != "verilog" && format != "blif" {
+        return Err(Error::Usage(format!(
+            "export format must be verilog or blif, got {}",
+            format
+        )));
+    }
+    let mut path = None;
+    let mut repair = false;
+    for arg in args {
+        match arg.as_str() {
+            "--repair" => repair = true,
+            _ if path.is_none() => path = Some(arg.as_str()),
+            _ => return Err(Error::Usage(format!("unexpected argument: {}", arg))),
+        }
+    }
+
+    let input = read_input(24, path).map_err(Error::Run)?;
+    let mut device = Day24::parse(&input).map_err(|error| Error::Run(error.to_string()))?;
+    if repair {
+        let report = device
+            .check_adder()
+            .map_err(|error| Error::Run(error.to_string()))?;
+        for (a, b) in &report.swaps {
+            eprintln!("swapping {} and {}", a, b);
+            device
+                .swap_outputs(a, b)
+                .map_err(|error| Error::Run(error.to_string()))?;
+        }
+    }
+
+    if format == "verilog" {
+        print!("{}", device.verilog());
+    } else {
+        print!("{}", device.blif());
+    }
+    Ok(())
+}
+
+fn read_input(day: u8, path: Option<&str>) -> Result<String, String> {
+    let mut input = String::new();
+
+    match path {
+        None => return input::load(day).map_err(|error| error.to_string()),
+        Some("-") => {
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|error| format!("cannot read stdin: {}", error))?;
+        }
+        Some(path) => {
+            input = std::fs::read_to_string(path)
+                .map_err(|error| format!("cannot read {}: {}", path, error))?
+        }
+    }
+
+    // editors and `curl` leave a trailing newline, none of the parsers expect it
+    Ok(input.trim_end().to_string())
+}
@@ -2,72 +2,108 @@
 
 use std::collections::{hash_map::Entry, HashMap};
 
-pub fn solution_part1(input: &str) -> usize {
-    let mut lists = Lists::from_input(input);
-
-    lists.left.sort();
-    lists.right.sort();
-
-    lists
-        .left
-        .iter()
-        .zip(lists.right)
-        .map(|(left, right)| left.abs_diff(right))
-        .sum()
+use crate::{solution::Solution, ParseError, Source};
+
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let lists = Lists::from_input(input)?;
+    Ok(lists.total_distance())
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let lists = Lists::from_input(input);
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let lists = Lists::from_input(input)?;
+    Ok(lists.similarity_score())
+}
 
-    let mut right_match_map = HashMap::<usize, usize>::new();
+pub struct Day01;
 
-    for right in lists.right {
-        match right_match_map.entry(right) {
-            Entry::Vacant(vacant_entry) => {
-                vacant_entry.insert(1);
-            }
-            Entry::Occupied(mut occupied_entry) => {
-                *occupied_entry.get_mut() += 1;
-            }
-        }
-    }
+impl Solution for Day01 {
+    const DAY: u8 = 1;
 
-    let mut sum = 0;
+    type Parsed<'a> = Lists;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    for left in lists.left {
-        let how_many_times = right_match_map.get(&left);
+    fn parse(input: &str) -> Result<Lists, ParseError> {
+        Lists::from_input(input)
+    }
 
-        if let Some(how_many_times) = how_many_times {
-            sum += left * how_many_times;
-        }
+    fn part1(lists: Lists, _: &()) -> usize {
+        lists.total_distance()
     }
 
-    sum
+    fn part2(lists: Lists, _: &()) -> usize {
+        lists.similarity_score()
+    }
 }
 
-struct Lists {
+pub struct Lists {
     left: Vec<usize>,
     right: Vec<usize>,
 }
 
 impl Lists {
-    fn from_input(input: &str) -> Self {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
         let (left_list, right_list) = input
             .lines()
-            .map(|l| l.split_once("   ").unwrap())
-            .map(|(l, r)| (l.parse::<usize>().unwrap(), r.parse::<usize>().unwrap()))
+            .map(|l| {
+                let (l, r) = source.split_once(l, "   ")?;
+                Ok((source.number::<usize>(l)?, source.number::<usize>(r)?))
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?
+            .into_iter()
             .unzip();
 
-        Self {
+        Ok(Self {
             left: left_list,
             right: right_list,
+        })
+    }
+
+    fn total_distance(mut self) -> usize {
+        self.left.sort();
+        self.right.sort();
+
+        self.left
+            .iter()
+            .zip(self.right)
+            .map(|(left, right)| left.abs_diff(right))
+            .sum()
+    }
+
+    fn similarity_score(self) -> usize {
+        let mut right_match_map = HashMap::<usize, usize>::new();
+
+        for right in self.right {
+            match right_match_map.entry(right) {
+                Entry::Vacant(vacant_entry) => {
+                    vacant_entry.insert(1);
+                }
+                Entry::Occupied(mut occupied_entry) => {
+                    *occupied_entry.get_mut() += 1;
+                }
+            }
         }
+
+        let mut sum = 0;
+
+        for left in self.left {
+            let how_many_times = right_match_map.get(&left);
+
+            if let Some(how_many_times) = how_many_times {
+                sum += left * how_many_times;
+            }
+        }
+
+        sum
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "3   4
 4   3
@@ -76,25 +112,33 @@ mod tests {
 3   9
 3   3";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 11);
+        assert_eq!(solution_part1(EXAMPLE), Ok(11));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 1258579);
+        let expected = real_answer!(1, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(1)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE), 31);
+        assert_eq!(solution_part2(EXAMPLE), Ok(31));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 23981443);
+        let expected = real_answer!(1, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(1)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
@@ -1,28 +1,58 @@
 // https://adventofcode.com/2024/day/2
 
-pub fn solution_part1(input: &str) -> usize {
-    let reports = Reports::from_input(input);
-    reports.count_safe(false)
+use crate::{solution::Solution, ParseError, Source};
+
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let reports = Reports::from_input(input)?;
+    Ok(reports.count_safe(false))
+}
+
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let reports = Reports::from_input(input)?;
+    Ok(reports.count_safe(true))
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let reports = Reports::from_input(input);
-    reports.count_safe(true)
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Parsed<'a> = Reports;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Reports, ParseError> {
+        Reports::from_input(input)
+    }
+
+    fn part1(reports: Reports, _: &()) -> usize {
+        reports.count_safe(false)
+    }
+
+    fn part2(reports: Reports, _: &()) -> usize {
+        reports.count_safe(true)
+    }
 }
 
 struct Report {
     levels: Vec<usize>,
 }
 
-struct Reports {
+pub struct Reports {
     list: Vec<Report>,
 }
 
 impl Reports {
-    fn from_input(input: &str) -> Self {
-        Self {
-            list: input.lines().map(Report::from_input_line).collect(),
-        }
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+
+        Ok(Self {
+            list: input
+                .lines()
+                .map(|line| Report::from_input_line(&source, line))
+                .collect::<Result<_, _>>()?,
+        })
     }
 
     fn count_safe(self, with_problem_dampener: bool) -> usize {
@@ -34,18 +64,17 @@ impl Reports {
 }
 
 impl Report {
-    fn from_input_line(line: &str) -> Self {
-        Self {
+    fn from_input_line(source: &Source, line: &str) -> Result<Self, ParseError> {
+        Ok(Self {
             levels: line
                 .split_whitespace()
-                .map(str::parse::<usize>)
-                .map(Result::unwrap)
-                .collect(),
-        }
+                .map(|level| source.number::<usize>(level))
+                .collect::<Result<_, _>>()?,
+        })
     }
 
     fn is_safe(&self, with_problem_dampener: bool) -> bool {
-        return are_levels_safe(&self.levels, with_problem_dampener);
+        are_levels_safe(&self.levels, with_problem_dampener)
     }
 }
 
@@ -73,7 +102,7 @@ fn are_levels_safe(levels: &[usize], with_problem_dampener: bool) -> bool {
         prev_level = *level;
     }
 
-    return true;
+    true
 }
 
 trait SkipIndex<T> {
@@ -93,6 +122,7 @@ impl<T: Copy> SkipIndex<T> for &[T] {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "7 6 4 2 1
 1 2 7 8 9
@@ -101,25 +131,33 @@ mod tests {
 8 6 4 4 1
 1 3 6 7 9";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 2);
+        assert_eq!(solution_part1(EXAMPLE), Ok(2));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 356);
+        let expected = real_answer!(2, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(2)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE), 4);
+        assert_eq!(solution_part2(EXAMPLE), Ok(4));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 413);
+        let expected = real_answer!(2, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(2)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
@@ -2,6 +2,8 @@
 
 use std::iter::Peekable;
 
+use crate::{solution::Solution, ParseError};
+
 macro_rules! check_char {
     ($c:expr,$chars:ident) => {
         if let Some($c) = $chars.peek() {
@@ -81,13 +83,35 @@ pub fn solution_part2(input: &str) -> usize {
     sum
 }
 
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Parsed<'a> = &'a str;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(memory: &str, _: &()) -> usize {
+        solution_part1(memory)
+    }
+
+    fn part2(memory: &str, _: &()) -> usize {
+        solution_part2(memory)
+    }
+}
+
 fn get_numbers<T: Iterator<Item = char>>(chars: &mut Peekable<T>) -> Option<usize> {
-    if let Some(c) = chars.peek() {
+    {
+        let c = chars.peek()?;
         if !c.is_numeric() {
             return None;
         }
-    } else {
-        return None;
     }
 
     let mut num: usize = 0;
@@ -107,22 +131,23 @@ fn get_numbers<T: Iterator<Item = char>>(chars: &mut Peekable<T>) -> Option<usiz
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE1: &str =
         "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
     const EXAMPLE2: &str =
         "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
         assert_eq!(solution_part1(EXAMPLE1), 161);
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 173529487);
+        let expected = real_answer!(3, Part::One);
+        assert_eq!(solution_part1(&real_input!(3)).to_string(), expected);
     }
 
     #[test]
@@ -131,7 +156,9 @@ mod tests {
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 99532691);
+        let expected = real_answer!(3, Part::Two);
+        assert_eq!(solution_part2(&real_input!(3)).to_string(), expected);
     }
 }
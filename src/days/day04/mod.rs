@@ -1,31 +1,51 @@
 // https://adventofcode.com/2024/day/4
 
-use input_iterators::{DiagonalBlTr, DiagonalBrTl, Horizontal, Vertical, WordSearchItem};
+use crate::{solution::Solution, Grid, ParseError, Position, Source};
 
-mod input_iterators;
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let word_finder = WordFinder::from_input(input)?;
+    Ok(word_finder.count_word("xmas"))
+}
 
-pub fn solution_part1(input: &str) -> usize {
-    let word_finder = WordFinder::new(input);
-    word_finder.count_word("xmas")
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let word_finder = WordFinder::from_input(input)?;
+    Ok(word_finder.count_x_word("mas"))
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let word_finder = WordFinder::new(input);
-    word_finder.count_x_word("mas")
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Parsed<'a> = WordFinder;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<WordFinder, ParseError> {
+        WordFinder::from_input(input)
+    }
+
+    fn part1(word_finder: WordFinder, _: &()) -> usize {
+        word_finder.count_word("xmas")
+    }
+
+    fn part2(word_finder: WordFinder, _: &()) -> usize {
+        word_finder.count_x_word("mas")
+    }
 }
 
-struct WordFinder {
-    lines: Vec<Vec<char>>,
+pub struct WordFinder {
+    letters: Grid<char>,
 }
 
 impl WordFinder {
-    fn new(text: &str) -> Self {
-        Self {
-            lines: text
-                .lines()
-                .map(|line| line.chars().collect::<Vec<char>>())
-                .collect::<Vec<_>>(),
-        }
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+
+        Ok(Self {
+            letters: Grid::from_input(&source, input, |c, _| Ok(c))?,
+        })
     }
 
     fn count_word(&self, word: &str) -> usize {
@@ -33,21 +53,21 @@ impl WordFinder {
         let mut found: usize = 0;
 
         // horizontal left to right
-        found += find_matches(Horizontal::new(&self.lines), &letters, false).len();
+        found += find_matches(self.letters.rows(), &letters, false).len();
         // horizontal right to left
-        found += find_matches(Horizontal::new(&self.lines), &reverse(&letters), false).len();
+        found += find_matches(self.letters.rows(), &reverse(&letters), false).len();
         // vertical top to bottom
-        found += find_matches(Vertical::new(&self.lines), &letters, false).len();
+        found += find_matches(self.letters.columns(), &letters, false).len();
         // vertical bottom to top
-        found += find_matches(Vertical::new(&self.lines), &reverse(&letters), false).len();
+        found += find_matches(self.letters.columns(), &reverse(&letters), false).len();
         // diagonal bottom-right to top-left
-        found += find_matches(DiagonalBrTl::new(&self.lines), &letters, false).len();
+        found += find_matches(self.letters.diagonals(), &letters, false).len();
         // diagonal top-left to bottom-right
-        found += find_matches(DiagonalBrTl::new(&self.lines), &reverse(&letters), false).len();
+        found += find_matches(self.letters.diagonals(), &reverse(&letters), false).len();
         // diagonal bottom-left to top-right
-        found += find_matches(DiagonalBlTr::new(&self.lines), &letters, false).len();
+        found += find_matches(self.letters.anti_diagonals(), &letters, false).len();
         // diagonal top-right to bottom-left
-        found += find_matches(DiagonalBlTr::new(&self.lines), &reverse(&letters), false).len();
+        found += find_matches(self.letters.anti_diagonals(), &reverse(&letters), false).len();
 
         found
     }
@@ -60,47 +80,47 @@ impl WordFinder {
         let letters: Vec<char> = word.chars().collect();
 
         // diagonal bottom-right to top-left
-        let br_tl = find_matches(DiagonalBrTl::new(&self.lines), &letters, false);
+        let br_tl = find_matches(self.letters.diagonals(), &letters, false);
         // diagonal top-left to bottom-right
-        let tl_br = find_matches(DiagonalBrTl::new(&self.lines), &reverse(&letters), false);
+        let tl_br = find_matches(self.letters.diagonals(), &reverse(&letters), false);
         // diagonal bottom-left to top-right
-        let bl_tr = find_matches(DiagonalBlTr::new(&self.lines), &letters, false);
+        let bl_tr = find_matches(self.letters.anti_diagonals(), &letters, false);
         // diagonal top-right to bottom-left
-        let tr_bl = find_matches(DiagonalBlTr::new(&self.lines), &reverse(&letters), false);
+        let tr_bl = find_matches(self.letters.anti_diagonals(), &reverse(&letters), false);
 
         let centers_br_tl = br_tl
             .into_iter()
-            .map(|m| m[m.len() / 2])
-            .chain(tl_br.into_iter().map(|m| m[m.len() / 2]));
+            .map(|mut m| m.swap_remove(m.len() / 2))
+            .chain(tl_br.into_iter().map(|mut m| m.swap_remove(m.len() / 2)));
 
         let centers_bl_tr = bl_tr
             .into_iter()
-            .map(|m| m[m.len() / 2])
-            .chain(tr_bl.into_iter().map(|m| m[m.len() / 2]))
+            .map(|mut m| m.swap_remove(m.len() / 2))
+            .chain(tr_bl.into_iter().map(|mut m| m.swap_remove(m.len() / 2)))
             .collect::<Vec<_>>();
 
         centers_br_tl
             .into_iter()
-            .filter(|i| centers_bl_tr.contains(&i))
+            .filter(|i| centers_bl_tr.contains(i))
             .count()
     }
 }
 
 fn find_matches<'a>(
-    iter: impl Iterator<Item = WordSearchItem>,
-    letters: &Vec<char>,
+    lines: impl Iterator<Item = impl Iterator<Item = (Position, &'a char)>>,
+    letters: &[char],
     print: bool,
-) -> Vec<Vec<(usize, usize)>> {
+) -> Vec<Vec<Position>> {
     let word_length = letters.len();
-    let mut matches: Vec<Vec<(usize, usize)>> = vec![];
+    let mut matches: Vec<Vec<Position>> = vec![];
     let mut current_match = vec![];
 
     if print {
         println!("Finding matches for {:?}", letters);
     }
 
-    for item in iter {
-        if let WordSearchItem::Letter(c, pos) = item {
+    for line in lines {
+        for (pos, c) in line {
             if print {
                 print!("{}", c);
             }
@@ -122,28 +142,25 @@ fn find_matches<'a>(
                     current_match.push(pos);
                 }
             }
-        } else {
-            current_match.clear();
-            if print {
-                println!();
-            }
         }
-    }
 
-    if print {
-        println!();
+        current_match.clear();
+        if print {
+            println!();
+        }
     }
 
     matches
 }
 
-fn reverse(letters: &Vec<char>) -> Vec<char> {
-    letters.iter().rev().map(|l| *l).collect()
+fn reverse(letters: &[char]) -> Vec<char> {
+    letters.iter().rev().copied().collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "MMMSXXMASM
 MSAMXMSMSA
@@ -156,25 +173,33 @@ SAXAMASAAA
 MAMMMXMMMM
 MXMXAXMASX";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 18);
+        assert_eq!(solution_part1(EXAMPLE), Ok(18));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 2521);
+        let expected = real_answer!(4, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(4)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE), 9);
+        assert_eq!(solution_part2(EXAMPLE), Ok(9));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 1912);
+        let expected = real_answer!(4, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(4)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
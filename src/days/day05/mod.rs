@@ -2,25 +2,42 @@
 
 use std::collections::HashSet;
 
-pub fn solution_part1(input: &str) -> usize {
-    let printer = Printer::from_input(input);
-    let correct_updates = printer.get_correct_updates();
-    correct_updates
-        .into_iter()
-        .map(|update| update.pages_to_produce[update.pages_to_produce.len() / 2])
-        .sum()
+use crate::{solution::Solution, ParseError, Source};
+
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let printer = Printer::from_input(input)?;
+    Ok(printer.sum_middle_pages_of_correct_updates())
+}
+
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let printer = Printer::from_input(input)?;
+    Ok(printer.sum_middle_pages_of_incorrect_updates_corrected())
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let printer = Printer::from_input(input);
-    let correct_updates = printer.get_incorrect_updates_corrected();
-    correct_updates
-        .into_iter()
-        .map(|update| update.pages_to_produce[update.pages_to_produce.len() / 2])
-        .sum()
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Parsed<'a> = Printer;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Printer, ParseError> {
+        Printer::from_input(input)
+    }
+
+    fn part1(printer: Printer, _: &()) -> usize {
+        printer.sum_middle_pages_of_correct_updates()
+    }
+
+    fn part2(printer: Printer, _: &()) -> usize {
+        printer.sum_middle_pages_of_incorrect_updates_corrected()
+    }
 }
 
-struct Printer {
+pub struct Printer {
     page_ordering_rules: HashSet<(usize, usize)>,
     updates: Vec<Update>,
 }
@@ -30,38 +47,51 @@ struct Update {
 }
 
 impl Printer {
-    fn from_input(input: &str) -> Self {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
         let mut found_separator = false;
         let mut page_ordering_rules = HashSet::new();
         let mut updates = vec![];
 
-        input.lines().for_each(|line| {
+        for line in input.lines() {
             if line.is_empty() {
                 found_separator = true;
-                return;
+                continue;
             }
 
             if found_separator {
                 let update = Update {
                     pages_to_produce: line
                         .split(',')
-                        .map(|n| n.parse::<usize>().unwrap())
-                        .collect::<Vec<_>>(),
+                        .map(|n| source.number::<usize>(n))
+                        .collect::<Result<Vec<_>, _>>()?,
                 };
                 updates.push(update);
             } else {
-                page_ordering_rules.insert(
-                    line.split_once('|')
-                        .map(|(l, r)| (l.parse::<usize>().unwrap(), r.parse::<usize>().unwrap()))
-                        .unwrap(),
-                );
+                let (l, r) = source.split_once(line, "|")?;
+                page_ordering_rules
+                    .insert((source.number::<usize>(l)?, source.number::<usize>(r)?));
             }
-        });
+        }
 
-        Self {
+        Ok(Self {
             page_ordering_rules,
             updates,
-        }
+        })
+    }
+
+    fn sum_middle_pages_of_correct_updates(self) -> usize {
+        self.get_correct_updates()
+            .iter()
+            .map(Update::middle_page)
+            .sum()
+    }
+
+    fn sum_middle_pages_of_incorrect_updates_corrected(self) -> usize {
+        self.get_incorrect_updates_corrected()
+            .iter()
+            .map(Update::middle_page)
+            .sum()
     }
 
     fn get_correct_updates(self) -> Vec<Update> {
@@ -86,14 +116,15 @@ impl Printer {
 }
 
 impl Update {
+    fn middle_page(&self) -> usize {
+        self.pages_to_produce[self.pages_to_produce.len() / 2]
+    }
+
     fn is_correct(&self, rules: &HashSet<(usize, usize)>) -> bool {
-        let mut i = 0;
-        for _ in 0..(self.pages_to_produce.len() - 1) {
+        for i in 0..(self.pages_to_produce.len() - 1) {
             if !rules.contains(&(self.pages_to_produce[i], self.pages_to_produce[i + 1])) {
                 return false;
             }
-
-            i += 1;
         }
 
         true
@@ -101,19 +132,14 @@ impl Update {
 
     fn correct(mut self, rules: &HashSet<(usize, usize)>) -> Self {
         loop {
-            let mut i = 0;
-            for _ in 0..(self.pages_to_produce.len() - 1) {
+            for i in 0..(self.pages_to_produce.len() - 1) {
                 if !rules.contains(&(self.pages_to_produce[i], self.pages_to_produce[i + 1])) {
-                    let temp = self.pages_to_produce[i + 1];
-                    self.pages_to_produce[i + 1] = self.pages_to_produce[i];
-                    self.pages_to_produce[i] = temp;
+                    self.pages_to_produce.swap(i + 1, i);
 
                     if self.is_correct(rules) {
                         return self;
                     }
                 }
-
-                i += 1;
             }
         }
     }
@@ -122,6 +148,7 @@ impl Update {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "47|53
 97|13
@@ -152,25 +179,33 @@ mod tests {
 61,13,29
 97,13,75,29,47";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 143);
+        assert_eq!(solution_part1(EXAMPLE), Ok(143));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 6949);
+        let expected = real_answer!(5, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(5)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE), 123);
+        assert_eq!(solution_part2(EXAMPLE), Ok(123));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 4145);
+        let expected = real_answer!(5, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(5)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
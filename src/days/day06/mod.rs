@@ -2,27 +2,45 @@
 
 use std::collections::HashSet;
 
-pub fn solution_part1(input: &str) -> usize {
-    let lab = Lab::from_input(input);
-    lab.count_patrolled_positions()
+use crate::{solution::Solution, Direction, Distance, Grid, ParseError, Position, Source};
+
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let lab = Lab::from_input(input)?;
+    Ok(lab.count_patrolled_positions())
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let lab = Lab::from_input(input);
-    lab.count_obstructions_to_loop()
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let lab = Lab::from_input(input)?;
+    Ok(lab.count_obstructions_to_loop())
 }
 
-#[derive(Clone)]
-struct Lab {
-    map: LabMap,
-    guard: Guard,
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Parsed<'a> = Lab;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Lab, ParseError> {
+        Lab::from_input(input)
+    }
+
+    fn part1(lab: Lab, _: &()) -> usize {
+        lab.count_patrolled_positions()
+    }
+
+    fn part2(lab: Lab, _: &()) -> usize {
+        lab.count_obstructions_to_loop()
+    }
 }
 
 #[derive(Clone)]
-struct LabMap {
-    obstructions: Vec<Vec<bool>>,
-    width: usize,
-    height: usize,
+pub struct Lab {
+    obstructions: Grid<bool>,
+    guard: Guard,
 }
 
 #[derive(Clone)]
@@ -33,67 +51,32 @@ struct Guard {
 
 #[derive(Clone)]
 enum GuardPosition {
-    InMap((usize, usize)),
+    InMap(Position),
     OutsideMap,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    UP,
-    RIGHT,
-    DOWN,
-    LEFT,
-}
-
-impl Direction {
-    fn turn_right(&self) -> Direction {
-        match self {
-            Direction::UP => Direction::RIGHT,
-            Direction::RIGHT => Direction::DOWN,
-            Direction::DOWN => Direction::LEFT,
-            Direction::LEFT => Direction::UP,
-        }
-    }
-}
-
 impl Lab {
-    fn from_input(input: &str) -> Self {
-        let mut obstructions: Vec<Vec<bool>> = vec![];
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
         let mut guard = None;
-        let mut width = 0;
-        let mut height = 0;
-
-        for (y, line) in input.lines().enumerate() {
-            height += 1;
-            width = line.len();
-            let mut obstructions_line = vec![false; width];
-
-            for (x, c) in line.chars().enumerate() {
-                match c {
-                    '#' => {
-                        obstructions_line[x] = true;
-                    }
-                    '^' => {
-                        guard = Some(Guard {
-                            position: GuardPosition::InMap((x, y)),
-                            direction: Direction::UP,
-                        });
-                    }
-                    _ => {}
-                }
-            }
 
-            obstructions.push(obstructions_line);
-        }
+        let obstructions = Grid::from_input(&source, input, |c, position| match c {
+            '.' => Ok(false),
+            '#' => Ok(true),
+            '^' if guard.is_none() => {
+                guard = Some(Guard {
+                    position: GuardPosition::InMap(position),
+                    direction: Direction::Up,
+                });
+                Ok(false)
+            }
+            _ => Err("`.`, `#` or a single `^`"),
+        })?;
 
-        Self {
-            guard: guard.expect("Guard to be in the input"),
-            map: LabMap {
-                obstructions,
-                width,
-                height,
-            },
-        }
+        Ok(Self {
+            guard: guard.ok_or_else(|| source.end("the guard `^`"))?,
+            obstructions,
+        })
     }
 
     fn count_patrolled_positions(mut self) -> usize {
@@ -101,7 +84,7 @@ impl Lab {
     }
 
     fn count_obstructions_to_loop(mut self) -> usize {
-        let GuardPosition::InMap(start_position) = self.guard.position else {
+        let GuardPosition::InMap(start_position) = self.guard.position.clone() else {
             unreachable!("Always starts in map");
         };
         let start_guard_position = self.guard.clone();
@@ -113,59 +96,44 @@ impl Lab {
                 continue;
             }
 
-            self.guard.direction = start_guard_position.direction.clone();
+            self.guard.direction = start_guard_position.direction;
             self.guard.position = start_guard_position.position.clone();
 
-            self.map.obstructions[position.1][position.0] = true;
+            self.obstructions[&position] = true;
             if self.check_for_loop() {
                 obstructions_count += 1;
             }
-            self.map.obstructions[position.1][position.0] = false;
+            self.obstructions[&position] = false;
         }
 
         obstructions_count
     }
 
-    fn patrol_to_end(&mut self) -> HashSet<(usize, usize)> {
+    fn patrol_to_end(&mut self) -> HashSet<Position> {
         let mut patrolled_cases = HashSet::new();
 
-        loop {
-            let GuardPosition::InMap(pos) = self.guard.position else {
-                break;
-            };
-
-            if patrolled_cases.contains(&(pos.0, pos.1, self.guard.direction)) {
+        while let GuardPosition::InMap(pos) = &self.guard.position {
+            if !patrolled_cases.insert((pos.clone(), self.guard.direction)) {
                 break;
             }
 
-            patrolled_cases.insert((pos.0, pos.1, self.guard.direction));
-            self.guard.move_step(&self.map);
+            self.guard.move_step(&self.obstructions);
         }
 
-        let patrolled_positions = patrolled_cases
+        patrolled_cases
             .into_iter()
-            .map(|c| (c.0, c.1))
-            .collect::<HashSet<(usize, usize)>>();
-
-        patrolled_positions
+            .map(|c| c.0)
+            .collect::<HashSet<Position>>()
     }
 
     fn check_for_loop(&mut self) -> bool {
         let mut hit_obstructions = HashSet::new();
 
-        loop {
-            let GuardPosition::InMap(_) = self.guard.position else {
-                break;
-            };
-
-            if let Some(hit_obstruction) = self.guard.move_step(&self.map) {
-                let key = (hit_obstruction.0, hit_obstruction.1, self.guard.direction);
-
-                if hit_obstructions.contains(&key) {
+        while let GuardPosition::InMap(_) = self.guard.position {
+            if let Some(hit_obstruction) = self.guard.move_step(&self.obstructions) {
+                if !hit_obstructions.insert((hit_obstruction, self.guard.direction)) {
                     return true;
                 }
-
-                hit_obstructions.insert(key);
             }
         }
 
@@ -174,37 +142,25 @@ impl Lab {
 }
 
 impl Guard {
-    fn move_step(&mut self, map: &LabMap) -> Option<(usize, usize)> {
+    fn move_step(&mut self, obstructions: &Grid<bool>) -> Option<Position> {
         let mut hit_obstruction = None;
 
-        loop {
-            let GuardPosition::InMap(pos) = self.position else {
-                break;
-            };
-
-            let movement: (isize, isize) = match self.direction {
-                Direction::UP => (0, -1),
-                Direction::RIGHT => (1, 0),
-                Direction::DOWN => (0, 1),
-                Direction::LEFT => (-1, 0),
-            };
-
-            let new_x = pos.0 as isize + movement.0;
-            let new_y = pos.1 as isize + movement.1;
-
-            if (pos.0 as isize) < -movement.0
-                || (pos.0 as isize) >= (map.width as isize - movement.0)
-                || (pos.1 as isize) < -movement.1
-                || (pos.1 as isize) >= (map.height as isize - movement.1)
-            {
-                self.position = GuardPosition::OutsideMap;
-                break;
-            } else if map.obstructions[new_y as usize][new_x as usize] {
-                self.direction = self.direction.turn_right();
-                hit_obstruction = Some((new_x as usize, new_y as usize));
-            } else {
-                self.position = GuardPosition::InMap((new_x as usize, new_y as usize));
-                break;
+        while let GuardPosition::InMap(pos) = &self.position {
+            let movement = Distance::from_direction(&self.direction);
+
+            match pos.move_by(&movement, obstructions.width(), obstructions.height()) {
+                None => {
+                    self.position = GuardPosition::OutsideMap;
+                    break;
+                }
+                Some(new_pos) if obstructions[&new_pos] => {
+                    self.direction = self.direction.turn_right();
+                    hit_obstruction = Some(new_pos);
+                }
+                Some(new_pos) => {
+                    self.position = GuardPosition::InMap(new_pos);
+                    break;
+                }
             }
         }
 
@@ -215,6 +171,7 @@ impl Guard {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "....#.....
 .........#
@@ -227,25 +184,55 @@ mod tests {
 #.........
 ......#...";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 41);
+        assert_eq!(solution_part1(EXAMPLE), Ok(41));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            solution_part1("..#\n.x^"),
+            Err(ParseError {
+                line: 2,
+                column: 2,
+                expected: "`.`, `#` or a single `^`".to_string(),
+                found: Some("x".to_string()),
+            })
+        );
+        assert_eq!(
+            solution_part1("..#\n..."),
+            Err(ParseError {
+                line: 2,
+                column: 4,
+                expected: "the guard `^`".to_string(),
+                found: None,
+            })
+        );
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 4973);
+        let expected = real_answer!(6, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(6)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE), 6);
+        assert_eq!(solution_part2(EXAMPLE), Ok(6));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 1482);
+        let expected = real_answer!(6, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(6)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
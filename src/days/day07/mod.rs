@@ -1,16 +1,41 @@
 // https://adventofcode.com/2024/day/7
 
-pub fn solution_part1(input: &str) -> usize {
-    let calibrations = Calibrations::from_input(input);
-    calibrations.sum_possible_calibration_results()
+use crate::{solution::Solution, ParseError, Source};
+
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let calibrations = Calibrations::from_input(input)?;
+    Ok(calibrations.sum_possible_calibration_results())
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let calibrations = Calibrations::from_input(input);
-    calibrations.sum_possible_calibration_results_with_concat()
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let calibrations = Calibrations::from_input(input)?;
+    Ok(calibrations.sum_possible_calibration_results_with_concat())
 }
 
-struct Calibrations {
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Parsed<'a> = Calibrations;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Calibrations, ParseError> {
+        Calibrations::from_input(input)
+    }
+
+    fn part1(calibrations: Calibrations, _: &()) -> usize {
+        calibrations.sum_possible_calibration_results()
+    }
+
+    fn part2(calibrations: Calibrations, _: &()) -> usize {
+        calibrations.sum_possible_calibration_results_with_concat()
+    }
+}
+
+pub struct Calibrations {
     incomplete_equations: Vec<IncompleteEquation>,
 }
 
@@ -20,28 +45,26 @@ struct IncompleteEquation {
 }
 
 impl Calibrations {
-    fn from_input(input: &str) -> Self {
-        Self {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+
+        Ok(Self {
             incomplete_equations: input
                 .lines()
                 .map(|line| {
-                    let (left, right) = line.split_once(':').expect("to have a colon");
+                    let (left, right) = source.split_once(line, ":")?;
 
-                    IncompleteEquation {
-                        result: left.parse::<usize>().expect("left to be usize"),
+                    Ok(IncompleteEquation {
+                        result: source.number::<usize>(left)?,
                         factors: right
                             .trim()
                             .split(' ')
-                            .map(|factor| {
-                                factor
-                                    .parse::<usize>()
-                                    .expect("right to be usizes separated by space")
-                            })
-                            .collect::<Vec<_>>(),
-                    }
+                            .map(|factor| source.number::<usize>(factor))
+                            .collect::<Result<Vec<_>, _>>()?,
+                    })
                 })
-                .collect::<Vec<_>>(),
-        }
+                .collect::<Result<Vec<_>, _>>()?,
+        })
     }
 
     fn sum_possible_calibration_results(&self) -> usize {
@@ -109,24 +132,24 @@ fn can_be_true_with_concat(
 fn fast_concat(left: usize, right: usize) -> usize {
     let right_digits: u32 = match right {
         0..10 => 1,
-        0..100 => 2,
-        0..1000 => 3,
-        0..10000 => 4,
-        0..100000 => 5,
-        0..1000000 => 6,
-        0..10000000 => 7,
-        0..100000000 => 8,
-        0..1000000000 => 9,
-        0..10000000000 => 10,
-        0..100000000000 => 11,
-        0..1000000000000 => 12,
-        0..10000000000000 => 13,
-        0..100000000000000 => 14,
-        0..1000000000000000 => 15,
-        0..10000000000000000 => 16,
-        0..100000000000000000 => 17,
-        0..1000000000000000000 => 18,
-        0..10000000000000000000 => 19,
+        10..100 => 2,
+        100..1000 => 3,
+        1000..10000 => 4,
+        10000..100000 => 5,
+        100000..1000000 => 6,
+        1000000..10000000 => 7,
+        10000000..100000000 => 8,
+        100000000..1000000000 => 9,
+        1000000000..10000000000 => 10,
+        10000000000..100000000000 => 11,
+        100000000000..1000000000000 => 12,
+        1000000000000..10000000000000 => 13,
+        10000000000000..100000000000000 => 14,
+        100000000000000..1000000000000000 => 15,
+        1000000000000000..10000000000000000 => 16,
+        10000000000000000..100000000000000000 => 17,
+        100000000000000000..1000000000000000000 => 18,
+        1000000000000000000..10000000000000000000 => 19,
         _ => unreachable!(),
     };
 
@@ -136,6 +159,7 @@ fn fast_concat(left: usize, right: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "190: 10 19
 3267: 81 40 27
@@ -147,25 +171,33 @@ mod tests {
 21037: 9 7 18 13
 292: 11 6 16 20";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 3749);
+        assert_eq!(solution_part1(EXAMPLE), Ok(3749));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 12553187650171);
+        let expected = real_answer!(7, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(7)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE), 11387);
+        assert_eq!(solution_part2(EXAMPLE), Ok(11387));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 96779702119491);
+        let expected = real_answer!(7, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(7)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
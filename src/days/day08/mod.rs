@@ -2,22 +2,43 @@
 
 use std::collections::{hash_map::Entry, HashMap};
 
-use crate::{Distance, Position};
+use crate::{solution::Solution, Distance, Grid, ParseError, Position, Source};
 
-pub fn solution_part1(input: &str) -> usize {
-    let grid = CityGrid::from_input(input);
-    grid.count_antinodes()
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let grid = CityGrid::from_input(input)?;
+    Ok(grid.count_antinodes())
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let grid = CityGrid::from_input(input);
-    grid.count_antinodes_extended()
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let grid = CityGrid::from_input(input)?;
+    Ok(grid.count_antinodes_extended())
 }
 
-struct CityGrid {
-    width: usize,
-    height: usize,
-    antennas: Vec<Antenna>,
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Parsed<'a> = CityGrid;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<CityGrid, ParseError> {
+        CityGrid::from_input(input)
+    }
+
+    fn part1(grid: CityGrid, _: &()) -> usize {
+        grid.count_antinodes()
+    }
+
+    fn part2(grid: CityGrid, _: &()) -> usize {
+        grid.count_antinodes_extended()
+    }
+}
+
+pub struct CityGrid {
+    map: Grid<char>,
 }
 
 struct Antenna {
@@ -26,35 +47,29 @@ struct Antenna {
 }
 
 impl CityGrid {
-    fn from_input(input: &str) -> Self {
-        let lines = input.lines().collect::<Vec<_>>();
-
-        Self {
-            width: lines[0].len(),
-            height: lines.len(),
-            antennas: lines
-                .into_iter()
-                .enumerate()
-                .flat_map(|(y, line)| {
-                    line.chars().enumerate().filter_map(move |(x, cell)| {
-                        if cell != '.' {
-                            Some(Antenna {
-                                position: Position(x, y),
-                                frequency: cell,
-                            })
-                        } else {
-                            None
-                        }
-                    })
-                })
-                .collect::<Vec<_>>(),
-        }
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+
+        Ok(Self {
+            map: Grid::from_input(&source, input, |c, _| Ok(c))?,
+        })
+    }
+
+    fn antennas(&self) -> Vec<Antenna> {
+        self.map
+            .iter()
+            .filter(|(_, cell)| **cell != '.')
+            .map(|(position, cell)| Antenna {
+                position,
+                frequency: *cell,
+            })
+            .collect::<Vec<_>>()
     }
 
     fn count_antinodes(self) -> usize {
-        let antennas_by_frequency = group_by_frequency(self.antennas);
+        let antennas_by_frequency = group_by_frequency(self.antennas());
 
-        let mut antinode_cells: Vec<bool> = vec![false; self.width * self.height];
+        let mut antinode_cells = Grid::new(self.map.width(), self.map.height(), false);
 
         for antennas in antennas_by_frequency.into_values() {
             for antenna1 in antennas.iter() {
@@ -69,32 +84,32 @@ impl CityGrid {
                     let negative_distance =
                         Distance::between(&antenna2.position, &antenna1.position);
 
-                    if let Some(antinode1_pos) =
-                        antenna1
-                            .position
-                            .move_by(&negative_distance, self.width, self.height)
-                    {
-                        antinode_cells[antinode1_pos.0 * self.height + antinode1_pos.1] = true;
+                    if let Some(antinode1_pos) = antenna1.position.move_by(
+                        &negative_distance,
+                        self.map.width(),
+                        self.map.height(),
+                    ) {
+                        antinode_cells[&antinode1_pos] = true;
                     }
 
                     if let Some(antinode2_pos) =
                         antenna2
                             .position
-                            .move_by(&distance, self.width, self.height)
+                            .move_by(&distance, self.map.width(), self.map.height())
                     {
-                        antinode_cells[antinode2_pos.0 * self.height + antinode2_pos.1] = true;
+                        antinode_cells[&antinode2_pos] = true;
                     }
                 }
             }
         }
 
-        antinode_cells.into_iter().filter(|cell| *cell).count()
+        antinode_cells.iter().filter(|(_, cell)| **cell).count()
     }
 
     fn count_antinodes_extended(self) -> usize {
-        let antennas_by_frequency = group_by_frequency(self.antennas);
+        let antennas_by_frequency = group_by_frequency(self.antennas());
 
-        let mut antinode_cells: Vec<bool> = vec![false; self.width * self.height];
+        let mut antinode_cells = Grid::new(self.map.width(), self.map.height(), false);
 
         for antennas in antennas_by_frequency.into_values() {
             for antenna1 in antennas.iter() {
@@ -105,8 +120,8 @@ impl CityGrid {
                         continue;
                     }
 
-                    antinode_cells[antenna1.position.0 * self.height + antenna1.position.1] = true;
-                    antinode_cells[antenna2.position.0 * self.height + antenna2.position.1] = true;
+                    antinode_cells[&antenna1.position] = true;
+                    antinode_cells[&antenna2.position] = true;
 
                     let distance = Distance::between(&antenna1.position, &antenna2.position);
                     let negative_distance =
@@ -115,27 +130,27 @@ impl CityGrid {
                     let mut count = 1;
                     while let Some(antinode1_pos) = antenna1.position.move_by(
                         &negative_distance.multiply(count),
-                        self.width,
-                        self.height,
+                        self.map.width(),
+                        self.map.height(),
                     ) {
-                        antinode_cells[antinode1_pos.0 * self.height + antinode1_pos.1] = true;
+                        antinode_cells[&antinode1_pos] = true;
                         count += 1;
                     }
 
                     let mut count = 1;
                     while let Some(antinode2_pos) = antenna2.position.move_by(
                         &distance.multiply(count),
-                        self.width,
-                        self.height,
+                        self.map.width(),
+                        self.map.height(),
                     ) {
-                        antinode_cells[antinode2_pos.0 * self.height + antinode2_pos.1] = true;
+                        antinode_cells[&antinode2_pos] = true;
                         count += 1;
                     }
                 }
             }
         }
 
-        antinode_cells.into_iter().filter(|cell| *cell).count()
+        antinode_cells.iter().filter(|(_, cell)| **cell).count()
     }
 }
 
@@ -159,6 +174,7 @@ fn group_by_frequency(antennas: Vec<Antenna>) -> HashMap<char, Vec<Antenna>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const PRE_EXAMPLE: &str = "..........
 ...#......
@@ -184,30 +200,38 @@ mod tests {
 ............
 ............";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_pre_example() {
-        assert_eq!(solution_part1(PRE_EXAMPLE), 2);
+        assert_eq!(solution_part1(PRE_EXAMPLE), Ok(2));
     }
 
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 14);
+        assert_eq!(solution_part1(EXAMPLE), Ok(14));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 295);
+        let expected = real_answer!(8, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(8)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE), 34);
+        assert_eq!(solution_part2(EXAMPLE), Ok(34));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 1034);
+        let expected = real_answer!(8, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(8)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
@@ -1,26 +1,57 @@
 // https://adventofcode.com/2024/day/9
 
-pub fn solution_part1(input: &str) -> usize {
-    let disk_map = DiskMap::from_input(input);
-    disk_map.checksum_after_compacting_individual_blocks()
+use crate::{solution::Solution, ParseError, Source};
+
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let disk_map = DiskMap::from_input(input)?;
+    Ok(disk_map.checksum_after_compacting_individual_blocks())
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let disk_map = DiskMap::from_input(input);
-    disk_map.checksum_after_compacting_files()
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let disk_map = DiskMap::from_input(input)?;
+    Ok(disk_map.checksum_after_compacting_files())
 }
 
-struct DiskMap {
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Parsed<'a> = DiskMap;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<DiskMap, ParseError> {
+        DiskMap::from_input(input)
+    }
+
+    fn part1(disk_map: DiskMap, _: &()) -> usize {
+        disk_map.checksum_after_compacting_individual_blocks()
+    }
+
+    fn part2(disk_map: DiskMap, _: &()) -> usize {
+        disk_map.checksum_after_compacting_files()
+    }
+}
+
+pub struct DiskMap {
     structure: Vec<Option<usize>>,
 }
 
 impl DiskMap {
-    fn from_input(input: &str) -> Self {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
         let mut is_in_files = true;
         let mut id = 0;
         let mut structure = vec![];
 
-        for n in input.chars().filter_map(|c| c.to_digit(10)) {
+        let disk_map = input.trim_end();
+        for (i, c) in disk_map.char_indices() {
+            let Some(n) = c.to_digit(10) else {
+                return Err(source.char_error(&disk_map[i..], "a digit"));
+            };
+
             if is_in_files {
                 for _ in 0..n {
                     structure.push(Some(id));
@@ -36,7 +67,7 @@ impl DiskMap {
             is_in_files = !is_in_files;
         }
 
-        Self { structure }
+        Ok(Self { structure })
     }
 
     fn checksum_after_compacting_individual_blocks(&self) -> usize {
@@ -78,7 +109,7 @@ impl DiskMap {
                     break;
                 }
 
-                if free_space.fits(&file) {
+                if free_space.fits(file) {
                     file.move_into(free_space);
                     break;
                 }
@@ -173,28 +204,37 @@ fn group_in_files_and_free_spaces(structure: &[Option<usize>]) -> (Vec<File>, Ve
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "2333133121414131402";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 1928);
+        assert_eq!(solution_part1(EXAMPLE), Ok(1928));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 6378826667552);
+        let expected = real_answer!(9, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(9)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE), 2858);
+        assert_eq!(solution_part2(EXAMPLE), Ok(2858));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 6413328569890);
+        let expected = real_answer!(9, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(9)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
@@ -2,32 +2,52 @@
 
 use std::collections::HashSet;
 
-pub fn solution_part1(input: &str) -> usize {
-    let map = TopographicMap::from_input(input);
-    map.count_hiking_trails()
+use crate::{solution::Solution, Grid, ParseError, Position, Source};
+
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let map = TopographicMap::from_input(input)?;
+    Ok(map.count_hiking_trails())
+}
+
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let map = TopographicMap::from_input(input)?;
+    Ok(map.rate_trailheads())
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let map = TopographicMap::from_input(input);
-    map.rate_trailheads()
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Parsed<'a> = TopographicMap;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<TopographicMap, ParseError> {
+        TopographicMap::from_input(input)
+    }
+
+    fn part1(map: TopographicMap, _: &()) -> usize {
+        map.count_hiking_trails()
+    }
+
+    fn part2(map: TopographicMap, _: &()) -> usize {
+        map.rate_trailheads()
+    }
 }
 
-struct TopographicMap {
-    heights: Vec<Vec<u32>>,
+pub struct TopographicMap {
+    heights: Grid<u32>,
 }
 
 impl TopographicMap {
-    fn from_input(input: &str) -> Self {
-        Self {
-            heights: input
-                .lines()
-                .map(|l| {
-                    l.chars()
-                        .map(|c| c.to_digit(10).expect("all to be a digit"))
-                        .collect::<Vec<_>>()
-                })
-                .collect::<Vec<_>>(),
-        }
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+
+        Ok(Self {
+            heights: Grid::from_input(&source, input, |c, _| c.to_digit(10).ok_or("a digit"))?,
+        })
     }
 
     fn count_hiking_trails(&self) -> usize {
@@ -39,15 +59,11 @@ impl TopographicMap {
     }
 
     fn count_trails(&self, repeats: bool) -> usize {
-        let trailheads: Vec<(usize, usize)> = self
+        let trailheads: Vec<Position> = self
             .heights
             .iter()
-            .enumerate()
-            .flat_map(|(y, l)| {
-                l.iter()
-                    .enumerate()
-                    .filter_map(move |(x, h)| if *h == 0 { Some((x, y)) } else { None })
-            })
+            .filter(|(_, h)| **h == 0)
+            .map(|(position, _)| position)
             .collect::<Vec<_>>();
 
         let mut found = 0;
@@ -55,51 +71,19 @@ impl TopographicMap {
             let mut trails = vec![(0, trailhead)];
             let mut tops = HashSet::new();
 
-            while let Some(trailpoint) = trails.pop() {
-                if trailpoint.0 == 9 {
-                    if repeats || tops.insert(trailpoint.1) {
+            while let Some((height, position)) = trails.pop() {
+                if height == 9 {
+                    if repeats || tops.insert(position) {
                         found += 1;
                     }
                     continue;
                 }
 
-                // left
-                match (trailpoint.1 .0.checked_sub(1), trailpoint.1 .1) {
-                    (None, _) => {}
-                    (Some(x), y) => {
-                        if self.heights[y][x] == trailpoint.0 + 1 {
-                            trails.push((trailpoint.0 + 1, (x, y)));
-                        }
-                    }
-                }
-
-                // up
-                match (trailpoint.1 .0, trailpoint.1 .1.checked_sub(1)) {
-                    (_, None) => {}
-                    (x, Some(y)) => {
-                        if self.heights[y][x] == trailpoint.0 + 1 {
-                            trails.push((trailpoint.0 + 1, (x, y)));
-                        }
+                for next_position in self.heights.neighbours(&position) {
+                    if self.heights[&next_position] == height + 1 {
+                        trails.push((height + 1, next_position));
                     }
                 }
-
-                // right
-                let (x, y) = (
-                    (trailpoint.1 .0 + 1).min(self.heights[0].len() - 1),
-                    trailpoint.1 .1,
-                );
-                if self.heights[y][x] == trailpoint.0 + 1 {
-                    trails.push((trailpoint.0 + 1, (x, y)));
-                }
-
-                // down
-                let (x, y) = (
-                    trailpoint.1 .0,
-                    (trailpoint.1 .1 + 1).min(self.heights.len() - 1),
-                );
-                if self.heights[y][x] == trailpoint.0 + 1 {
-                    trails.push((trailpoint.0 + 1, (x, y)));
-                }
             }
         }
 
@@ -110,6 +94,7 @@ impl TopographicMap {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const PRE_EXAMPLE: &str = "1110111
 1111111
@@ -128,30 +113,38 @@ mod tests {
 01329801
 10456732";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_pre_example() {
-        assert_eq!(solution_part1(PRE_EXAMPLE), 2);
+        assert_eq!(solution_part1(PRE_EXAMPLE), Ok(2));
     }
 
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 36);
+        assert_eq!(solution_part1(EXAMPLE), Ok(36));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 652);
+        let expected = real_answer!(10, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(10)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE), 81);
+        assert_eq!(solution_part2(EXAMPLE), Ok(81));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 1432);
+        let expected = real_answer!(10, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(10)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
@@ -2,30 +2,56 @@
 
 use std::collections::HashMap;
 
-pub fn solution_part1(input: &str) -> usize {
-    let stones = Stones::from_input(input);
-    stones.count_stones_after_blinking(25)
+use crate::{solution::Solution, ParseError, Source};
+
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let stones = Stones::from_input(input)?;
+    Ok(stones.count_stones_after_blinking(25))
+}
+
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let stones = Stones::from_input(input)?;
+    Ok(stones.count_stones_after_blinking(75))
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let stones = Stones::from_input(input);
-    stones.count_stones_after_blinking(75)
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Parsed<'a> = Stones;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Stones, ParseError> {
+        Stones::from_input(input)
+    }
+
+    fn part1(stones: Stones, _: &()) -> usize {
+        stones.count_stones_after_blinking(25)
+    }
+
+    fn part2(stones: Stones, _: &()) -> usize {
+        stones.count_stones_after_blinking(75)
+    }
 }
 
-struct Stones {
+pub struct Stones {
     line: Vec<usize>,
 }
 
 impl Stones {
-    fn from_input(input: &str) -> Self {
-        Self {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+
+        Ok(Self {
             line: input
                 .trim()
                 .split(' ')
-                .map(str::parse::<usize>)
-                .map(Result::unwrap)
-                .collect::<Vec<_>>(),
-        }
+                .map(|stone| source.number::<usize>(stone))
+                .collect::<Result<Vec<_>, _>>()?,
+        })
     }
 
     fn count_stones_after_blinking(&self, times: usize) -> usize {
@@ -87,23 +113,32 @@ fn cut_even_number_of_digits(stone: usize) -> Option<(usize, usize)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "125 17";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 55312);
+        assert_eq!(solution_part1(EXAMPLE), Ok(55312));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 186996);
+        let expected = real_answer!(11, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(11)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 221683913164898);
+        let expected = real_answer!(11, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(11)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
@@ -2,19 +2,42 @@
 
 use std::collections::{btree_map::Entry, BTreeMap, BTreeSet};
 
-use crate::Position;
+use crate::{solution::Solution, Grid, ParseError, Position, Source};
 
-pub fn solution_part1(input: &str) -> usize {
-    let plots = GardenPlots::from_input(input);
-    plots.calculate_price_of_fencing()
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let plots = GardenPlots::from_input(input)?;
+    Ok(plots.calculate_price_of_fencing())
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let plots = GardenPlots::from_input(input);
-    plots.calculate_new_price_of_fencing()
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let plots = GardenPlots::from_input(input)?;
+    Ok(plots.calculate_new_price_of_fencing())
 }
 
-struct GardenPlots {
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Parsed<'a> = GardenPlots;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<GardenPlots, ParseError> {
+        GardenPlots::from_input(input)
+    }
+
+    fn part1(plots: GardenPlots, _: &()) -> usize {
+        plots.calculate_price_of_fencing()
+    }
+
+    fn part2(plots: GardenPlots, _: &()) -> usize {
+        plots.calculate_new_price_of_fencing()
+    }
+}
+
+pub struct GardenPlots {
     regions: Vec<Region>,
 }
 
@@ -24,26 +47,18 @@ struct Region {
 }
 
 impl GardenPlots {
-    fn from_input(input: &str) -> Self {
-        let mut all_position = BTreeSet::new();
-        let lines = input
-            .lines()
-            .map(|l| l.chars().collect::<Vec<_>>())
-            .collect::<Vec<Vec<char>>>();
-
-        for y in 0..lines.len() {
-            for x in 0..lines[0].len() {
-                all_position.insert(Position(x, y));
-            }
-        }
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+        let plots = Grid::from_input(&source, input, |c, _| Ok(c))?;
+        let mut all_position = plots.positions().collect::<BTreeSet<_>>();
 
         let mut regions = vec![];
 
         while let Some(position) = all_position.pop_first() {
-            regions.push(find_region(position, &mut all_position, &lines));
+            regions.push(find_region(position, &mut all_position, &plots));
         }
 
-        Self { regions }
+        Ok(Self { regions })
     }
 
     fn calculate_price_of_fencing(&self) -> usize {
@@ -173,56 +188,16 @@ impl Region {
 fn find_region(
     position: Position,
     available_positions: &mut BTreeSet<Position>,
-    lines: &Vec<Vec<char>>,
+    plots: &Grid<char>,
 ) -> Region {
-    let id = lines[position.1][position.0];
-    let width = lines[0].len();
-    let height = lines.len();
+    let id = plots[&position];
     let mut positions = BTreeSet::new();
     positions.insert(position.clone());
     let mut positions_to_check = vec![position];
 
     while let Some(position) = positions_to_check.pop() {
-        // left
-        if position.0 > 0 {
-            let new_position = Position(position.0 - 1, position.1);
-            if lines[new_position.1][new_position.0] == id
-                && available_positions.contains(&new_position)
-            {
-                available_positions.remove(&new_position);
-                positions.insert(new_position.clone());
-                positions_to_check.push(new_position);
-            }
-        }
-        // right
-        if position.0 < width - 1 {
-            let new_position = Position(position.0 + 1, position.1);
-            if lines[new_position.1][new_position.0] == id
-                && available_positions.contains(&new_position)
-            {
-                available_positions.remove(&new_position);
-                positions.insert(new_position.clone());
-                positions_to_check.push(new_position);
-            }
-        }
-        // up
-        if position.1 > 0 {
-            let new_position = Position(position.0, position.1 - 1);
-            if lines[new_position.1][new_position.0] == id
-                && available_positions.contains(&new_position)
-            {
-                available_positions.remove(&new_position);
-                positions.insert(new_position.clone());
-                positions_to_check.push(new_position);
-            }
-        }
-        // down
-        if position.1 < height - 1 {
-            let new_position = Position(position.0, position.1 + 1);
-            if lines[new_position.1][new_position.0] == id
-                && available_positions.contains(&new_position)
-            {
-                available_positions.remove(&new_position);
+        for new_position in plots.neighbours(&position) {
+            if plots[&new_position] == id && available_positions.remove(&new_position) {
                 positions.insert(new_position.clone());
                 positions_to_check.push(new_position);
             }
@@ -238,6 +213,7 @@ fn find_region(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE1: &str = "AAAA
 BBCD
@@ -274,55 +250,63 @@ ABBAAA
 ABBAAA
 AAAAAA";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example1() {
-        assert_eq!(solution_part1(EXAMPLE1), 140);
+        assert_eq!(solution_part1(EXAMPLE1), Ok(140));
     }
 
     #[test]
     fn test_part1_example2() {
-        assert_eq!(solution_part1(EXAMPLE2), 772);
+        assert_eq!(solution_part1(EXAMPLE2), Ok(772));
     }
 
     #[test]
     fn test_part1_example3() {
-        assert_eq!(solution_part1(EXAMPLE3), 1930);
+        assert_eq!(solution_part1(EXAMPLE3), Ok(1930));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 1424472);
+        let expected = real_answer!(12, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(12)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example1() {
-        assert_eq!(solution_part2(EXAMPLE1), 80);
+        assert_eq!(solution_part2(EXAMPLE1), Ok(80));
     }
 
     #[test]
     fn test_part2_example2() {
-        assert_eq!(solution_part2(EXAMPLE2), 436);
+        assert_eq!(solution_part2(EXAMPLE2), Ok(436));
     }
 
     #[test]
     fn test_part2_example3() {
-        assert_eq!(solution_part2(EXAMPLE3), 1206);
+        assert_eq!(solution_part2(EXAMPLE3), Ok(1206));
     }
 
     #[test]
     fn test_part2_example4() {
-        assert_eq!(solution_part2(EXAMPLE4), 236);
+        assert_eq!(solution_part2(EXAMPLE4), Ok(236));
     }
 
     #[test]
     fn test_part2_example5() {
-        assert_eq!(solution_part2(EXAMPLE5), 368);
+        assert_eq!(solution_part2(EXAMPLE5), Ok(368));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 870202);
+        let expected = real_answer!(12, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(12)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
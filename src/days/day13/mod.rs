@@ -1,20 +1,43 @@
 // https://adventofcode.com/2024/day/13
 
-pub fn solution_part1(input: &str) -> usize {
-    let claw_machine = ClawMachine::from_input(input);
-    claw_machine.minimum_credits_to_win_all_prizes()
+use crate::{solution::Solution, ParseError, Source};
+
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let claw_machine = ClawMachine::from_input(input)?;
+    Ok(claw_machine.minimum_credits_to_win_all_prizes())
+}
+
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let mut claw_machine = ClawMachine::from_input(input)?;
+    claw_machine.fix_unit_conversion_error();
+    Ok(claw_machine.minimum_credits_to_win_all_prizes())
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let mut claw_machine = ClawMachine::from_input(input);
-    for game in claw_machine.games.iter_mut() {
-        game.prize_at.0 += 10000000000000;
-        game.prize_at.1 += 10000000000000;
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Parsed<'a> = ClawMachine;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<ClawMachine, ParseError> {
+        ClawMachine::from_input(input)
+    }
+
+    fn part1(claw_machine: ClawMachine, _: &()) -> usize {
+        claw_machine.minimum_credits_to_win_all_prizes()
+    }
+
+    fn part2(mut claw_machine: ClawMachine, _: &()) -> usize {
+        claw_machine.fix_unit_conversion_error();
+        claw_machine.minimum_credits_to_win_all_prizes()
     }
-    claw_machine.minimum_credits_to_win_all_prizes()
 }
 
-struct ClawMachine {
+pub struct ClawMachine {
     games: Vec<ClawMachineGame>,
 }
 
@@ -26,12 +49,21 @@ struct ClawMachineGame {
 }
 
 impl ClawMachine {
-    fn from_input(input: &str) -> Self {
-        Self {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+
+        Ok(Self {
             games: input
                 .split("\n\n")
-                .map(|game| ClawMachineGame::from_input(game))
-                .collect::<Vec<_>>(),
+                .map(|game| ClawMachineGame::from_input(&source, game))
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+
+    fn fix_unit_conversion_error(&mut self) {
+        for game in self.games.iter_mut() {
+            game.prize_at.0 += 10000000000000;
+            game.prize_at.1 += 10000000000000;
         }
     }
 
@@ -44,76 +76,25 @@ impl ClawMachine {
 }
 
 impl ClawMachineGame {
-    fn from_input(input: &str) -> Self {
-        let [a_str, b_str, prize_at_str] = input.splitn(3, '\n').collect::<Vec<_>>()[..] else {
-            unreachable!();
+    fn from_input(source: &Source, input: &str) -> Result<Self, ParseError> {
+        let mut lines = input.lines();
+        let mut next_line = |expected: &str| {
+            lines
+                .next()
+                .ok_or_else(|| source.error(&input[input.len()..], expected))
         };
-        let mut button_a = (0, 0);
-        let mut button_b = (0, 0);
-        let mut prize_at = (0, 0);
-        //Button A: X+*, Y+*
-        let mut c_iter = a_str.chars().skip(12);
-        while let Some(c) = c_iter.next() {
-            if let Some(d) = c.to_digit(10) {
-                button_a.0 = button_a.0 * 10 + d as usize;
-            } else {
-                break;
-            }
-        }
-        c_iter.next();
-        c_iter.next();
-        c_iter.next();
-        while let Some(c) = c_iter.next() {
-            if let Some(d) = c.to_digit(10) {
-                button_a.1 = button_a.1 * 10 + d as usize;
-            } else {
-                break;
-            }
-        }
-        //Button B: X+*, Y+*
-        let mut c_iter = b_str.chars().skip(12);
-        while let Some(c) = c_iter.next() {
-            if let Some(d) = c.to_digit(10) {
-                button_b.0 = button_b.0 * 10 + d as usize;
-            } else {
-                break;
-            }
-        }
-        c_iter.next();
-        c_iter.next();
-        c_iter.next();
-        while let Some(c) = c_iter.next() {
-            if let Some(d) = c.to_digit(10) {
-                button_b.1 = button_b.1 * 10 + d as usize;
-            } else {
-                break;
-            }
-        }
-        //Prize: X=*, Y=*
-        let mut c_iter = prize_at_str.chars().skip(9);
-        while let Some(c) = c_iter.next() {
-            if let Some(d) = c.to_digit(10) {
-                prize_at.0 = prize_at.0 * 10 + d as usize;
-            } else {
-                break;
-            }
-        }
-        c_iter.next();
-        c_iter.next();
-        c_iter.next();
-        while let Some(c) = c_iter.next() {
-            if let Some(d) = c.to_digit(10) {
-                prize_at.1 = prize_at.1 * 10 + d as usize;
-            } else {
-                break;
-            }
-        }
 
-        Self {
+        let button_a =
+            parse_coordinates(source, next_line("`Button A: `")?, "Button A: X+", ", Y+")?;
+        let button_b =
+            parse_coordinates(source, next_line("`Button B: `")?, "Button B: X+", ", Y+")?;
+        let prize_at = parse_coordinates(source, next_line("`Prize: `")?, "Prize: X=", ", Y=")?;
+
+        Ok(Self {
             button_a,
             button_b,
             prize_at,
-        }
+        })
     }
 
     fn minimum_credits_to_win2(&self) -> usize {
@@ -154,9 +135,20 @@ impl ClawMachineGame {
     }
 }
 
+fn parse_coordinates(
+    source: &Source,
+    line: &str,
+    x_prefix: &str,
+    y_separator: &str,
+) -> Result<(usize, usize), ParseError> {
+    let (x, y) = source.split_once(source.strip_prefix(line, x_prefix)?, y_separator)?;
+    Ok((source.number::<usize>(x)?, source.number::<usize>(y)?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "Button A: X+94, Y+34
 Button B: X+22, Y+67
@@ -174,20 +166,28 @@ Button A: X+69, Y+23
 Button B: X+27, Y+71
 Prize: X=18641, Y=10279";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 480);
+        assert_eq!(solution_part1(EXAMPLE), Ok(480));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 27157);
+        let expected = real_answer!(13, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(13)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 104015411578548);
+        let expected = real_answer!(13, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(13)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
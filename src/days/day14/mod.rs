@@ -1,31 +1,82 @@
 // https://adventofcode.com/2024/day/14
 
-use std::usize;
+use crate::{
+    solution::{Args, Params, Solution},
+    Distance, ParseError, Position, Source,
+};
+
+pub fn solution_part1(input: &str, width: usize, height: usize) -> Result<usize, ParseError> {
+    let robots = RobotsMovements::from_input(input)?;
+    Ok(robots.move_for_seconds_and_check_quadrants(100, width, height))
+}
+
+pub fn solution_part2(input: &str, width: usize, height: usize) -> Result<usize, ParseError> {
+    let robots = RobotsMovements::from_input(input)?;
+    Ok(robots.move_until_pattern(width, height))
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Parsed<'a> = RobotsMovements;
+    type Params = Area;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<RobotsMovements, ParseError> {
+        RobotsMovements::from_input(input)
+    }
+
+    fn part1(robots: RobotsMovements, area: &Area) -> usize {
+        robots.move_for_seconds_and_check_quadrants(100, area.width, area.height)
+    }
+
+    fn part2(robots: RobotsMovements, area: &Area) -> usize {
+        robots.move_until_pattern(area.width, area.height)
+    }
+}
 
-use crate::{Distance, Position};
+pub struct Area {
+    pub width: usize,
+    pub height: usize,
+}
 
-pub fn solution_part1(input: &str, width: usize, height: usize) -> usize {
-    let robots = RobotsMovements::from_input(input);
-    robots.move_for_seconds_and_check_quadrants(100, width, height)
+impl Default for Area {
+    fn default() -> Self {
+        Self {
+            width: 101,
+            height: 103,
+        }
+    }
 }
 
-pub fn solution_part2(input: &str, width: usize, height: usize) -> usize {
-    let robots = RobotsMovements::from_input(input);
-    robots.move_until_pattern(width, height)
+impl Params for Area {
+    fn from_args(args: &mut Args) -> Result<Self, String> {
+        let default = Self::default();
+
+        Ok(Self {
+            width: args.next_or("width", default.width)?,
+            height: args.next_or("height", default.height)?,
+        })
+    }
 }
 
-struct RobotsMovements {
+pub struct RobotsMovements {
     robots: Vec<RobotMovements>,
 }
 
 impl RobotsMovements {
-    fn from_input(input: &str) -> Self {
-        Self {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+
+        Ok(Self {
             robots: input
                 .lines()
-                .map(RobotMovements::from_input)
-                .collect::<Vec<_>>(),
-        }
+                .map(|line| RobotMovements::from_input(&source, line))
+                .collect::<Result<Vec<_>, _>>()?,
+        })
     }
 
     fn move_for_seconds_and_check_quadrants(
@@ -78,7 +129,7 @@ impl RobotsMovements {
 
                 how_many
             })
-            .fold(1, |acc, x| acc * x)
+            .product()
     }
 
     fn move_until_pattern(&self, width: usize, height: usize) -> usize {
@@ -110,29 +161,23 @@ struct RobotMovements {
 }
 
 impl RobotMovements {
-    fn from_input(input: &str) -> Self {
-        let (pos_input, vel_input) = input.trim().split_once(' ').unwrap();
-        let (pos_x_input, pos_y_input) = pos_input
-            .strip_prefix("p=")
-            .unwrap()
-            .split_once(',')
-            .unwrap();
-        let (vel_x_input, vel_y_input) = vel_input
-            .strip_prefix("v=")
-            .unwrap()
-            .split_once(',')
-            .unwrap();
-
-        Self {
+    fn from_input(source: &Source, input: &str) -> Result<Self, ParseError> {
+        let (pos_input, vel_input) = source.split_once(input.trim(), " ")?;
+        let (pos_x_input, pos_y_input) =
+            source.split_once(source.strip_prefix(pos_input, "p=")?, ",")?;
+        let (vel_x_input, vel_y_input) =
+            source.split_once(source.strip_prefix(vel_input, "v=")?, ",")?;
+
+        Ok(Self {
             pos: Position(
-                pos_x_input.parse::<usize>().unwrap(),
-                pos_y_input.parse::<usize>().unwrap(),
+                source.number::<usize>(pos_x_input)?,
+                source.number::<usize>(pos_y_input)?,
             ),
             velocity: Distance(
-                vel_x_input.parse::<isize>().unwrap(),
-                vel_y_input.parse::<isize>().unwrap(),
+                source.number::<isize>(vel_x_input)?,
+                source.number::<isize>(vel_y_input)?,
             ),
-        }
+        })
     }
 
     fn where_is_after_seconds(&self, seconds: usize, width: usize, height: usize) -> Position {
@@ -144,6 +189,7 @@ impl RobotMovements {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "p=0,4 v=3,-3
 p=6,3 v=-1,-3
@@ -158,20 +204,28 @@ p=7,3 v=-1,2
 p=2,4 v=2,-3
 p=9,5 v=-3,-3";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE, 11, 7), 12);
+        assert_eq!(solution_part1(EXAMPLE, 11, 7), Ok(12));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT, 101, 103), 229868730);
+        let expected = real_answer!(14, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(14), 101, 103).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT, 101, 103), 7861);
+        let expected = real_answer!(14, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(14), 101, 103).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
@@ -1,255 +1,211 @@
 // https://adventofcode.com/2024/day/15
 
-use std::collections::HashSet;
+use crate::{solution::Solution, Direction, Distance, Grid, ParseError, Position, Source};
 
-use crate::{Distance, Position};
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let warehouse_map = WarehouseMap::from_input(input)?;
+    Ok(warehouse_map.move_and_sum_gps_coordinates())
+}
 
-pub fn solution_part1(input: &str) -> usize {
-    let warehouse_map = WarehouseMap::from_input(input);
-    warehouse_map.move_and_sum_gps_coordinates()
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let warehouse_map = WarehouseMap::from_input(input)?;
+    Ok(warehouse_map.twice_as_wide_move_and_sum_gps_coordinates())
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let warehouse_map = WarehouseMap::from_input(input);
-    warehouse_map.twice_as_wide_move_and_sum_gps_coordinates()
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Parsed<'a> = WarehouseMap;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<WarehouseMap, ParseError> {
+        WarehouseMap::from_input(input)
+    }
+
+    fn part1(warehouse_map: WarehouseMap, _: &()) -> usize {
+        warehouse_map.move_and_sum_gps_coordinates()
+    }
+
+    fn part2(warehouse_map: WarehouseMap, _: &()) -> usize {
+        warehouse_map.twice_as_wide_move_and_sum_gps_coordinates()
+    }
 }
 
-struct WarehouseMap {
-    width: usize,
-    height: usize,
+pub struct WarehouseMap {
+    map: Grid<Tile>,
     start: Position,
-    boxes: Vec<Position>,
-    walls: Vec<Position>,
     moves: Vec<Direction>,
 }
 
-#[derive(Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Empty,
+    Wall,
+    Box,
+    BoxLeft,
+    BoxRight,
 }
 
 impl WarehouseMap {
-    fn from_input(input: &str) -> Self {
-        let (map_str, moves_str) = input.split_once("\n\n").unwrap();
-        let map_lines = map_str.lines().collect::<Vec<&str>>();
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+        let (map_str, moves_str) = source.split_once(input, "\n\n")?;
 
-        let width = map_lines[0].len() - 2;
-        let height = map_lines.len() - 2;
-
-        let mut boxes = vec![];
-        let mut walls = vec![];
-        let mut start = None;
+        let width = map_str.lines().next().map_or(0, |line| line.len());
+        let height = map_str.lines().count();
 
-        for i in 1..map_lines.len() - 1 {
-            let y = i - 1;
-            let tiles = map_lines[i].chars().collect::<Vec<char>>();
+        if width < 3 || height < 3 {
+            return Err(source.error(map_str, "a map surrounded by walls"));
+        }
 
-            for j in 1..tiles.len() - 1 {
-                let x = j - 1;
+        let mut start = None;
 
-                match tiles[j] {
-                    'O' => boxes.push(Position(x, y)),
-                    '#' => walls.push(Position(x, y)),
-                    '@' => start = Some(Position(x, y)),
-                    _ => {}
+        let map = Grid::from_input(&source, map_str, |tile, position| {
+            let is_border = position.0 == 0
+                || position.1 == 0
+                || position.0 == width - 1
+                || position.1 == height - 1;
+
+            match tile {
+                '#' => Ok(Tile::Wall),
+                _ if is_border => Err("`#`"),
+                'O' => Ok(Tile::Box),
+                '@' if start.is_none() => {
+                    start = Some(position);
+                    Ok(Tile::Empty)
                 }
+                '.' => Ok(Tile::Empty),
+                _ => Err("`.`, `#`, `O` or a single `@`"),
             }
-        }
+        })?;
 
         let moves = moves_str
-            .chars()
-            .filter_map(|c| match c {
-                '>' => Some(Direction::Right),
-                '^' => Some(Direction::Up),
-                '<' => Some(Direction::Left),
-                'v' => Some(Direction::Down),
-                _ => None,
+            .char_indices()
+            .filter(|(_, c)| !c.is_whitespace())
+            .map(|(i, c)| {
+                Direction::from_arrow(c)
+                    .ok_or_else(|| source.char_error(&moves_str[i..], "one of `^v<>`"))
             })
-            .collect::<Vec<_>>();
-
-        Self {
-            width,
-            height,
-            boxes,
-            walls,
-            start: start.unwrap(),
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            map,
+            start: start.ok_or_else(|| source.error(map_str, "the robot `@`"))?,
             moves,
-        }
+        })
     }
 
     fn move_and_sum_gps_coordinates(self) -> usize {
-        let width = self.width;
-        let height = self.height;
-        let mut boxes: HashSet<Position> = HashSet::from_iter(self.boxes.into_iter());
-        let walls: HashSet<Position> = HashSet::from_iter(self.walls.into_iter());
-        let mut current_pos = self.start.clone();
+        let mut map = self.map;
+        let mut current_pos = self.start;
 
-        let mut boxes_to_move: Vec<Position> = vec![];
         for movement in self.moves.iter() {
-            let distance = direction_to_distance(movement);
-
-            if let Some(tentative_pos) = current_pos.move_by(&distance, width, height) {
-                boxes_to_move.clear();
-
-                loop {
-                    let next_pos = match boxes_to_move
-                        .last()
-                        .map(|b| b.move_by(&distance, width, height))
-                    {
-                        Some(None) => {
-                            break;
-                        }
-                        None => tentative_pos.clone(),
-                        Some(Some(pos)) => pos,
-                    };
-
-                    if boxes.contains(&next_pos) {
-                        boxes_to_move.push(next_pos);
-                        continue;
-                    }
+            let distance = Distance::from_direction(movement);
+            let tentative_pos = step(&map, &current_pos, &distance);
 
-                    if walls.contains(&next_pos) {
-                        break;
-                    }
+            let mut free_pos = tentative_pos.clone();
+            while map[&free_pos] == Tile::Box {
+                free_pos = step(&map, &free_pos, &distance);
+            }
 
-                    if boxes_to_move.len() > 0 {
-                        boxes.remove(&boxes_to_move[0]);
-                        boxes.insert(next_pos.clone());
-                    }
-                    current_pos = tentative_pos;
-                    break;
-                }
+            if map[&free_pos] == Tile::Wall {
+                continue;
             }
+
+            if free_pos != tentative_pos {
+                map[&free_pos] = Tile::Box;
+                map[&tentative_pos] = Tile::Empty;
+            }
+            current_pos = tentative_pos;
         }
 
-        boxes
-            .into_iter()
-            .map(|pos| pos.0 + 1 + (pos.1 + 1) * 100)
-            .sum()
+        sum_gps_coordinates(&map, Tile::Box)
     }
 
     fn twice_as_wide_move_and_sum_gps_coordinates(self) -> usize {
-        let width = self.width * 2;
-        let height = self.height;
-
-        let mut boxes: HashSet<Box> = HashSet::from_iter(self.boxes.iter().map(|b| Box {
-            left: Position(b.0 * 2, b.1),
-            right: Position(b.0 * 2 + 1, b.1),
-        }));
-        let walls: HashSet<Position> =
-            HashSet::from_iter(self.walls.into_iter().flat_map(|wall| {
-                vec![
-                    Position(wall.0 * 2, wall.1),
-                    Position(wall.0 * 2 + 1, wall.1),
-                ]
-            }));
+        let mut map = Grid::new(self.map.width() * 2, self.map.height(), Tile::Empty);
+        for (position, tile) in self.map.iter() {
+            let (left, right) = match tile {
+                Tile::Box => (Tile::BoxLeft, Tile::BoxRight),
+                tile => (*tile, *tile),
+            };
+            map[&Position(position.0 * 2, position.1)] = left;
+            map[&Position(position.0 * 2 + 1, position.1)] = right;
+        }
         let mut current_pos = Position(self.start.0 * 2, self.start.1);
 
         for movement in self.moves.iter() {
-            let distance = direction_to_distance(movement);
-
-            let mut boxes_to_move: Vec<Box> = vec![];
-            let mut current_layer_of_boxes_to_move: Vec<Box> = vec![];
-
-            if let Some(tentative_pos) = current_pos.move_by(&distance, width, height) {
-                loop {
-                    let next_positions = if current_layer_of_boxes_to_move.len() != 0 {
-                        current_layer_of_boxes_to_move
-                            .iter()
-                            .filter_map(|box_to_move| box_to_move.move_by(&distance, width, height))
-                            .flat_map(|new_box| vec![new_box.left, new_box.right])
-                            .collect::<Vec<_>>()
-                    } else {
-                        vec![tentative_pos.clone()]
-                    };
-
-                    if next_positions.len() == 0 {
-                        break;
-                    }
+            let distance = Distance::from_direction(movement);
 
-                    current_layer_of_boxes_to_move.clear();
-
-                    let mut encountered_wall = false;
-                    let mut encountered_box = false;
-                    for next_pos in next_positions {
-                        if let Some(b) = boxes.iter().find(|b| {
-                            (b.left == next_pos || b.right == next_pos)
-                                && !boxes_to_move.contains(b)
-                        }) {
-                            current_layer_of_boxes_to_move.push(b.clone());
-                            boxes_to_move.push(b.clone());
-                            encountered_box = true;
-                            continue;
-                        }
-
-                        if walls.contains(&next_pos) {
-                            encountered_wall = true;
-                            break;
-                        }
-                    }
+            let mut tiles_to_move: Vec<Position> = vec![];
+            let mut tiles_to_check = vec![current_pos.clone()];
+            let mut encountered_wall = false;
 
-                    if encountered_wall {
+            while let Some(pos) = tiles_to_check.pop() {
+                let next_pos = step(&map, &pos, &distance);
+                let other_half = match map[&next_pos] {
+                    Tile::Wall => {
+                        encountered_wall = true;
                         break;
                     }
-
-                    if encountered_box {
-                        continue;
-                    }
-
-                    for b in boxes_to_move.iter() {
-                        boxes.remove(&b);
+                    Tile::BoxLeft => next_pos.right(),
+                    Tile::BoxRight => next_pos.left().expect("a box is never on the border"),
+                    Tile::Empty | Tile::Box => continue,
+                };
+
+                for half in [next_pos, other_half] {
+                    if !tiles_to_move.contains(&half) {
+                        tiles_to_move.push(half.clone());
+                        tiles_to_check.push(half);
                     }
+                }
+            }
 
-                    for b in boxes_to_move {
-                        boxes.insert(b.move_by(&distance, width, height).unwrap());
-                    }
+            if encountered_wall {
+                continue;
+            }
 
-                    current_pos = tentative_pos;
-                    break;
-                }
+            let moved_tiles = tiles_to_move
+                .into_iter()
+                .map(|pos| {
+                    let tile = map[&pos];
+                    map[&pos] = Tile::Empty;
+                    (step(&map, &pos, &distance), tile)
+                })
+                .collect::<Vec<_>>();
+
+            for (pos, tile) in moved_tiles {
+                map[&pos] = tile;
             }
+
+            current_pos = step(&map, &current_pos, &distance);
         }
 
-        boxes
-            .into_iter()
-            .map(|pos| pos.left.0 + 2 + (pos.left.1 + 1) * 100)
-            .sum()
+        sum_gps_coordinates(&map, Tile::BoxLeft)
     }
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Debug)]
-struct Box {
-    left: Position,
-    right: Position,
-}
-
-impl Box {
-    fn move_by(&self, distance: &Distance, width: usize, height: usize) -> Option<Box> {
-        match (
-            self.left.move_by(distance, width, height),
-            self.right.move_by(distance, width, height),
-        ) {
-            (Some(left), Some(right)) => Some(Box { left, right }),
-            _ => None,
-        }
-    }
+fn step(map: &Grid<Tile>, position: &Position, distance: &Distance) -> Position {
+    position
+        .move_by(distance, map.width(), map.height())
+        .expect("the map has walls all around so it won't ever go out")
 }
 
-fn direction_to_distance(dir: &Direction) -> Distance {
-    match dir {
-        Direction::Up => Distance(0, -1),
-        Direction::Down => Distance(0, 1),
-        Direction::Left => Distance(-1, 0),
-        Direction::Right => Distance(1, 0),
-    }
+fn sum_gps_coordinates(map: &Grid<Tile>, box_tile: Tile) -> usize {
+    map.iter()
+        .filter(|(_, tile)| **tile == box_tile)
+        .map(|(pos, _)| pos.0 + pos.1 * 100)
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const OTHER_EXAMPLE: &str = "#######
 #...#.#
@@ -294,35 +250,43 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_pre_example() {
-        assert_eq!(solution_part1(PRE_EXAMPLE), 2028);
+        assert_eq!(solution_part1(PRE_EXAMPLE), Ok(2028));
     }
 
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 10092);
+        assert_eq!(solution_part1(EXAMPLE), Ok(10092));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 1475249);
+        let expected = real_answer!(15, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(15)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_other_example() {
-        assert_eq!(solution_part2(OTHER_EXAMPLE), 618);
+        assert_eq!(solution_part2(OTHER_EXAMPLE), Ok(618));
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE), 9021);
+        assert_eq!(solution_part2(EXAMPLE), Ok(9021));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 1509724);
+        let expected = real_answer!(15, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(15)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
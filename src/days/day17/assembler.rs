@@ -0,0 +1,203 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::instruction::{Combo, Instruction};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssembleError {
+    pub line: usize,
+    pub kind: AssembleErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssembleErrorKind {
+    UnknownMnemonic(String),
+    MissingOperand(&'static str),
+    UnexpectedToken(String),
+    InvalidOperand(String),
+    OperandOutOfRange {
+        operand: String,
+        allowed: &'static str,
+    },
+    InvalidLabel(String),
+    DuplicateLabel(String),
+    UnknownLabel(String),
+    /// `jnz` takes a 3-bit literal, so only the first four instructions can be jumped to.
+    LabelOutOfRange {
+        label: String,
+        address: usize,
+    },
+}
+
+impl Display for AssembleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+
+        match &self.kind {
+            AssembleErrorKind::UnknownMnemonic(mnemonic) => {
+                write!(f, "unknown mnemonic `{}`", mnemonic)
+            }
+            AssembleErrorKind::MissingOperand(mnemonic) => {
+                write!(f, "`{}` needs an operand", mnemonic)
+            }
+            AssembleErrorKind::UnexpectedToken(token) => write!(f, "unexpected `{}`", token),
+            AssembleErrorKind::InvalidOperand(operand) => {
+                write!(f, "invalid operand `{}`", operand)
+            }
+            AssembleErrorKind::OperandOutOfRange { operand, allowed } => {
+                write!(
+                    f,
+                    "operand `{}` out of range, expected {}",
+                    operand, allowed
+                )
+            }
+            AssembleErrorKind::InvalidLabel(label) => write!(f, "invalid label `{}`", label),
+            AssembleErrorKind::DuplicateLabel(label) => {
+                write!(f, "label `{}` is already defined", label)
+            }
+            AssembleErrorKind::UnknownLabel(label) => write!(f, "unknown label `{}`", label),
+            AssembleErrorKind::LabelOutOfRange { label, address } => write!(
+                f,
+                "label `{}` is at address {}, out of reach of a 3-bit jump",
+                label, address
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AssembleError {}
+
+const LITERAL: &str = "0 to 7";
+const COMBO: &str = "0 to 3, A, B or C";
+
+struct Line<'a> {
+    number: usize,
+    mnemonic: &'a str,
+    operand: Option<&'a str>,
+}
+
+/// Assembles one instruction per line, `mnemonic [operand]`, into the numbers of a program.
+///
+/// Comments start with `;`. A line (possibly also holding an instruction) can start with
+/// `label:`, for `jnz` to use in place of an address.
+pub fn assemble(source: &str) -> Result<Vec<u8>, AssembleError> {
+    let mut labels = HashMap::new();
+    let mut lines = vec![];
+
+    for (i, line) in source.lines().enumerate() {
+        let number = i + 1;
+        let error = |kind| AssembleError { line: number, kind };
+
+        let mut line = line.split_once(';').map_or(line, |(code, _)| code).trim();
+
+        if let Some((label, rest)) = line.split_once(':') {
+            let label = label.trim();
+            if !is_label(label) {
+                return Err(error(AssembleErrorKind::InvalidLabel(label.to_string())));
+            }
+            if labels.insert(label, lines.len() * 2).is_some() {
+                return Err(error(AssembleErrorKind::DuplicateLabel(label.to_string())));
+            }
+            line = rest.trim();
+        }
+
+        let mut tokens = line.split_whitespace();
+        let Some(mnemonic) = tokens.next() else {
+            continue;
+        };
+        let operand = tokens.next();
+        if let Some(token) = tokens.next() {
+            return Err(error(AssembleErrorKind::UnexpectedToken(token.to_string())));
+        }
+
+        lines.push(Line {
+            number,
+            mnemonic,
+            operand,
+        });
+    }
+
+    lines
+        .iter()
+        .map(|line| encode(line, &labels))
+        .collect::<Result<Vec<_>, _>>()
+        .map(|instructions| instructions.iter().flat_map(Instruction::encode).collect())
+}
+
+fn encode(line: &Line, labels: &HashMap<&str, usize>) -> Result<Instruction, AssembleError> {
+    let error = |kind| AssembleError {
+        line: line.number,
+        kind,
+    };
+
+    let operand = |mnemonic: &'static str| {
+        line.operand
+            .ok_or_else(|| error(AssembleErrorKind::MissingOperand(mnemonic)))
+    };
+    let literal = |operand: &str| -> Result<u8, AssembleError> {
+        match operand.parse::<u8>() {
+            Ok(literal @ 0..=7) => Ok(literal),
+            Ok(_) => Err(error(AssembleErrorKind::OperandOutOfRange {
+                operand: operand.to_string(),
+                allowed: LITERAL,
+            })),
+            Err(_) => Err(error(AssembleErrorKind::InvalidOperand(
+                operand.to_string(),
+            ))),
+        }
+    };
+    let combo = |mnemonic: &'static str| -> Result<Combo, AssembleError> {
+        let operand = operand(mnemonic)?;
+
+        match operand {
+            "A" => Ok(Combo::A),
+            "B" => Ok(Combo::B),
+            "C" => Ok(Combo::C),
+            _ => match operand.parse::<u8>() {
+                Ok(literal @ 0..=3) => Ok(Combo::Literal(literal)),
+                Ok(_) => Err(error(AssembleErrorKind::OperandOutOfRange {
+                    operand: operand.to_string(),
+                    allowed: COMBO,
+                })),
+                Err(_) => Err(error(AssembleErrorKind::InvalidOperand(
+                    operand.to_string(),
+                ))),
+            },
+        }
+    };
+
+    Ok(match line.mnemonic {
+        "adv" => Instruction::Adv(combo("adv")?),
+        "bxl" => Instruction::Bxl(literal(operand("bxl")?)?),
+        "bst" => Instruction::Bst(combo("bst")?),
+        "jnz" => {
+            let target = operand("jnz")?;
+            match labels.get(target) {
+                Some(&address @ 0..=7) => Instruction::Jnz(address as u8),
+                Some(&address) => {
+                    return Err(error(AssembleErrorKind::LabelOutOfRange {
+                        label: target.to_string(),
+                        address,
+                    }))
+                }
+                None if is_label(target) => {
+                    return Err(error(AssembleErrorKind::UnknownLabel(target.to_string())))
+                }
+                None => Instruction::Jnz(literal(target)?),
+            }
+        }
+        "bxc" => Instruction::Bxc(line.operand.map_or(Ok(0), literal)?),
+        "out" => Instruction::Out(combo("out")?),
+        "bdv" => Instruction::Bdv(combo("bdv")?),
+        "cdv" => Instruction::Cdv(combo("cdv")?),
+        mnemonic => {
+            return Err(error(AssembleErrorKind::UnknownMnemonic(
+                mnemonic.to_string(),
+            )))
+        }
+    })
+}
+
+fn is_label(label: &str) -> bool {
+    label.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,303 @@
+use std::{
+    collections::BTreeSet,
+    fmt::Display,
+    io::{BufRead, Write},
+};
+
+use super::{instruction::Instruction, Program, VmError, Word};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Register {
+    A,
+    B,
+    C,
+}
+
+impl Register {
+    const ALL: [Register; 3] = [Register::A, Register::B, Register::C];
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Register::A => "A",
+            Register::B => "B",
+            Register::C => "C",
+        })
+    }
+}
+
+/// What happened while the debugger ran, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<W: Word = u64> {
+    /// `instruction`, at `address`, was executed.
+    Stepped {
+        address: usize,
+        instruction: Instruction,
+    },
+    Output(u8),
+    /// A watched register changed, with its values before and after the step.
+    Watchpoint {
+        register: Register,
+        old: W,
+        new: W,
+    },
+    /// The next instruction to run is at a breakpoint, it hasn't run yet.
+    Breakpoint(usize),
+    Halted,
+    /// The next instruction can't run, the program is stuck there.
+    Error(VmError),
+}
+
+/// Runs a [`Program`] one instruction at a time, each run returning the events it produced.
+pub struct Debugger<W: Word = u64> {
+    program: Program<W>,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeSet<Register>,
+    output: Vec<u8>,
+}
+
+impl<W: Word> Debugger<W> {
+    pub fn new(program: Program<W>) -> Self {
+        Self {
+            program,
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+            output: vec![],
+        }
+    }
+
+    pub fn registers(&self) -> &[W; 3] {
+        &self.program.registers
+    }
+
+    pub fn register(&self, register: Register) -> &W {
+        &self.program.registers[register.index()]
+    }
+
+    pub fn instruction_pointer(&self) -> usize {
+        self.program.instruction_pointer
+    }
+
+    /// The output produced so far.
+    pub fn output(&self) -> &[u8] {
+        &self.output
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.program.current_instruction().is_none()
+    }
+
+    pub fn program(&self) -> &Program<W> {
+        &self.program
+    }
+
+    /// Whether the breakpoint is new.
+    pub fn add_breakpoint(&mut self, address: usize) -> bool {
+        self.breakpoints.insert(address)
+    }
+
+    pub fn remove_breakpoint(&mut self, address: usize) -> bool {
+        self.breakpoints.remove(&address)
+    }
+
+    pub fn breakpoints(&self) -> &BTreeSet<usize> {
+        &self.breakpoints
+    }
+
+    pub fn watch(&mut self, register: Register) -> bool {
+        self.watchpoints.insert(register)
+    }
+
+    pub fn unwatch(&mut self, register: Register) -> bool {
+        self.watchpoints.remove(&register)
+    }
+
+    pub fn watchpoints(&self) -> &BTreeSet<Register> {
+        &self.watchpoints
+    }
+
+    /// Executes a single instruction, whatever the breakpoints.
+    pub fn step(&mut self) -> Vec<Event<W>> {
+        let mut events = vec![];
+        self.execute_one(&mut events);
+        events
+    }
+
+    /// Executes instructions until a breakpoint, a watchpoint or the end of the program.
+    pub fn resume(&mut self) -> Vec<Event<W>> {
+        self.run_until(|_| false)
+    }
+
+    /// Like [`Debugger::resume`], also stopping after the next `out`.
+    pub fn run_to_output(&mut self) -> Vec<Event<W>> {
+        self.run_until(|event| matches!(event, Event::Output(_)))
+    }
+
+    fn run_until(&mut self, mut stop: impl FnMut(&Event<W>) -> bool) -> Vec<Event<W>> {
+        let mut events = vec![];
+
+        loop {
+            let first = events.len();
+            if !self.execute_one(&mut events) {
+                return events;
+            }
+
+            let stopped = events[first..].iter().any(|event| {
+                stop(event) || matches!(event, Event::Watchpoint { .. } | Event::Halted)
+            });
+            if stopped {
+                return events;
+            }
+
+            let address = self.program.instruction_pointer;
+            if self.breakpoints.contains(&address) {
+                events.push(Event::Breakpoint(address));
+                return events;
+            }
+        }
+    }
+
+    /// Whether an instruction ran, pushing its events.
+    fn execute_one(&mut self, events: &mut Vec<Event<W>>) -> bool {
+        let Some(instruction) = self.program.current_instruction() else {
+            events.push(Event::Halted);
+            return false;
+        };
+
+        let address = self.program.instruction_pointer;
+        let before = self.program.registers.clone();
+        let output = match self.program.execute_instruction() {
+            Ok(output) => output.flatten(),
+            Err(error) => {
+                events.push(Event::Error(error));
+                return false;
+            }
+        };
+
+        events.push(Event::Stepped {
+            address,
+            instruction,
+        });
+        if let Some(output) = output {
+            self.output.push(output);
+            events.push(Event::Output(output));
+        }
+        for register in &self.watchpoints {
+            let (old, new) = (&before[register.index()], self.register(*register));
+            if old != new {
+                events.push(Event::Watchpoint {
+                    register: *register,
+                    old: old.clone(),
+                    new: new.clone(),
+                });
+            }
+        }
+        if self.is_halted() {
+            events.push(Event::Halted);
+        }
+
+        true
+    }
+}
+
+const HELP: &str = "Commands:
+  s, step             executes one instruction
+  c, continue         runs until a breakpoint, a watchpoint or the end
+  o, out              runs until the next output
+  b, break <address>  toggles a breakpoint
+  w, watch <A|B|C>    toggles a watchpoint
+  r, registers        shows the registers and the instruction pointer
+  p, print            shows the output so far
+  l, list             disassembles the program
+  q, quit";
+
+/// A line-based front-end to [`Debugger`], reading commands from `input` until `quit` or its end.
+pub fn repl<W: Word>(
+    program: Program<W>,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> std::io::Result<()> {
+    let mut debugger = Debugger::new(program);
+    writeln!(output, "{}", HELP)?;
+
+    for line in input.lines() {
+        let line = line?;
+        let mut words = line.split_whitespace();
+        let (command, argument) = (words.next().unwrap_or_default(), words.next());
+
+        match (command, argument) {
+            ("", _) => continue,
+            ("s" | "step", None) => print_events(&mut output, &debugger.step())?,
+            ("c" | "continue", None) => print_events(&mut output, &debugger.resume())?,
+            ("o" | "out", None) => print_events(&mut output, &debugger.run_to_output())?,
+            ("b" | "break", Some(address)) => match address.parse::<usize>() {
+                Ok(address) => {
+                    let added =
+                        debugger.add_breakpoint(address) || !debugger.remove_breakpoint(address);
+                    let state = if added { "set" } else { "removed" };
+                    writeln!(output, "breakpoint at {} {}", address, state)?;
+                }
+                Err(_) => writeln!(output, "invalid address: {}", address)?,
+            },
+            ("w" | "watch", Some(name)) => {
+                match Register::ALL.into_iter().find(|r| r.to_string() == name) {
+                    Some(register) => {
+                        let added = debugger.watch(register) || !debugger.unwatch(register);
+                        let state = if added { "watched" } else { "unwatched" };
+                        writeln!(output, "{} {}", register, state)?;
+                    }
+                    None => writeln!(output, "invalid register: {}", name)?,
+                }
+            }
+            ("r" | "registers", None) => {
+                let [a, b, c] = debugger.registers();
+                writeln!(
+                    output,
+                    "A={} B={} C={} ip={}",
+                    a,
+                    b,
+                    c,
+                    debugger.instruction_pointer()
+                )?;
+            }
+            ("p" | "print", None) => {
+                let printed = debugger
+                    .output()
+                    .iter()
+                    .map(u8::to_string)
+                    .collect::<Vec<_>>();
+                writeln!(output, "{}", printed.join(","))?;
+            }
+            ("l" | "list", None) => write!(output, "{}", debugger.program().disassemble())?,
+            ("q" | "quit", None) => return Ok(()),
+            _ => writeln!(output, "unknown command: {}\n{}", line.trim(), HELP)?,
+        }
+    }
+
+    Ok(())
+}
+
+fn print_events<W: Word>(output: &mut impl Write, events: &[Event<W>]) -> std::io::Result<()> {
+    for event in events {
+        match event {
+            Event::Stepped {
+                address,
+                instruction,
+            } => writeln!(output, "{:>4}  {}", address, instruction)?,
+            Event::Output(value) => writeln!(output, "      out: {}", value)?,
+            Event::Watchpoint { register, old, new } => {
+                writeln!(output, "      {}: {} -> {}", register, old, new)?
+            }
+            Event::Breakpoint(address) => writeln!(output, "breakpoint at {}", address)?,
+            Event::Halted => writeln!(output, "halted")?,
+            Event::Error(error) => writeln!(output, "error: {}", error)?,
+        }
+    }
+
+    Ok(())
+}
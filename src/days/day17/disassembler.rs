@@ -0,0 +1,91 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use super::{instruction::Instruction, Program, Word};
+
+/// A program decoded two numbers at a time, from its first instruction.
+pub struct Disassembly {
+    instructions: Vec<(usize, Instruction)>,
+    dangling: Option<u8>,
+}
+
+impl Disassembly {
+    /// Each instruction along with its address.
+    pub fn instructions(&self) -> &[(usize, Instruction)] {
+        &self.instructions
+    }
+
+    /// A last opcode that has no operand, the program halts before running it.
+    pub fn dangling(&self) -> Option<u8> {
+        self.dangling
+    }
+
+    /// Every address a `jnz` can jump to, including ones that aren't the start of an
+    /// instruction.
+    pub fn jump_targets(&self) -> BTreeSet<usize> {
+        self.instructions
+            .iter()
+            .filter_map(|(_, instruction)| match instruction {
+                Instruction::Jnz(target) => Some(*target as usize),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn is_label(&self, address: usize) -> bool {
+        address.is_multiple_of(2) && address / 2 < self.instructions.len()
+    }
+}
+
+/// Labels the jump targets that start an instruction, jumps elsewhere keep their number.
+impl Display for Disassembly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let targets = self.jump_targets();
+
+        for (address, instruction) in &self.instructions {
+            if targets.contains(address) {
+                writeln!(f, "L{}:", address)?;
+            }
+
+            let text = match instruction {
+                Instruction::Jnz(target) if self.is_label(*target as usize) => {
+                    format!("jnz L{}", target)
+                }
+                _ => instruction.to_string(),
+            };
+            let [opcode, operand] = instruction.encode();
+            writeln!(f, "    {:<8}; {}: {},{}", text, address, opcode, operand)?;
+        }
+
+        if let Some(opcode) = self.dangling {
+            writeln!(
+                f,
+                "    ; {}: {} without an operand",
+                self.instructions.len() * 2,
+                opcode
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: Word> Program<W> {
+    pub fn disassemble(&self) -> Disassembly {
+        let mut chunks = self.instructions.chunks_exact(2);
+
+        Disassembly {
+            instructions: chunks
+                .by_ref()
+                .enumerate()
+                .map(|(i, pair)| {
+                    (
+                        i * 2,
+                        Instruction::decode(pair[0], pair[1])
+                            .expect("programs to hold 3-bit numbers"),
+                    )
+                })
+                .collect(),
+            dangling: chunks.remainder().first().copied(),
+        }
+    }
+}
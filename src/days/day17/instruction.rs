@@ -0,0 +1,122 @@
+use std::fmt::Display;
+
+/// An operand read as a literal for `0..=3` and as a register for `4..=6`, `7` being reserved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combo {
+    Literal(u8),
+    A,
+    B,
+    C,
+    Reserved,
+}
+
+impl Combo {
+    pub fn decode(operand: u8) -> Self {
+        match operand {
+            0..=3 => Combo::Literal(operand),
+            4 => Combo::A,
+            5 => Combo::B,
+            6 => Combo::C,
+            _ => Combo::Reserved,
+        }
+    }
+
+    pub fn encode(&self) -> u8 {
+        match self {
+            Combo::Literal(literal) => *literal,
+            Combo::A => 4,
+            Combo::B => 5,
+            Combo::C => 6,
+            Combo::Reserved => 7,
+        }
+    }
+}
+
+impl Display for Combo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Combo::Literal(literal) => write!(f, "{}", literal),
+            Combo::A => write!(f, "A"),
+            Combo::B => write!(f, "B"),
+            Combo::C => write!(f, "C"),
+            Combo::Reserved => write!(f, "7"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Adv(Combo),
+    Bxl(u8),
+    Bst(Combo),
+    Jnz(u8),
+    /// Its operand is ignored, but kept so that the program encodes back to the same numbers.
+    Bxc(u8),
+    Out(Combo),
+    Bdv(Combo),
+    Cdv(Combo),
+}
+
+impl Instruction {
+    /// `None` unless both numbers have 3 bits.
+    pub fn decode(opcode: u8, operand: u8) -> Option<Self> {
+        if operand > 7 {
+            return None;
+        }
+
+        Some(match opcode {
+            0 => Instruction::Adv(Combo::decode(operand)),
+            1 => Instruction::Bxl(operand),
+            2 => Instruction::Bst(Combo::decode(operand)),
+            3 => Instruction::Jnz(operand),
+            4 => Instruction::Bxc(operand),
+            5 => Instruction::Out(Combo::decode(operand)),
+            6 => Instruction::Bdv(Combo::decode(operand)),
+            7 => Instruction::Cdv(Combo::decode(operand)),
+            _ => return None,
+        })
+    }
+
+    pub fn encode(&self) -> [u8; 2] {
+        match self {
+            Instruction::Adv(combo) => [0, combo.encode()],
+            Instruction::Bxl(literal) => [1, *literal],
+            Instruction::Bst(combo) => [2, combo.encode()],
+            Instruction::Jnz(literal) => [3, *literal],
+            Instruction::Bxc(ignored) => [4, *ignored],
+            Instruction::Out(combo) => [5, combo.encode()],
+            Instruction::Bdv(combo) => [6, combo.encode()],
+            Instruction::Cdv(combo) => [7, combo.encode()],
+        }
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Instruction::Adv(_) => "adv",
+            Instruction::Bxl(_) => "bxl",
+            Instruction::Bst(_) => "bst",
+            Instruction::Jnz(_) => "jnz",
+            Instruction::Bxc(_) => "bxc",
+            Instruction::Out(_) => "out",
+            Instruction::Bdv(_) => "bdv",
+            Instruction::Cdv(_) => "cdv",
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Adv(combo)
+            | Instruction::Bst(combo)
+            | Instruction::Out(combo)
+            | Instruction::Bdv(combo)
+            | Instruction::Cdv(combo) => write!(f, "{} {}", self.mnemonic(), combo),
+            Instruction::Bxl(literal) | Instruction::Jnz(literal) => {
+                write!(f, "{} {}", self.mnemonic(), literal)
+            }
+            Instruction::Bxc(0) => write!(f, "bxc"),
+            Instruction::Bxc(ignored) => write!(f, "bxc {}", ignored),
+        }
+    }
+}
@@ -1,170 +1,426 @@
 // https://adventofcode.com/2024/day/17
 
-pub fn solution_part1(input: &str) -> String {
-    let mut program = Program::from_input(input);
-    program.execute()
+pub use assembler::{assemble, AssembleError, AssembleErrorKind};
+pub use debugger::{repl, Debugger, Event, Register};
+pub use disassembler::Disassembly;
+pub use instruction::{Combo, Instruction};
+pub use quine::{QuineError, Quines};
+pub use trace::{Trace, TraceFormat, TraceRow};
+pub use word::Word;
+
+use std::{collections::HashSet, fmt::Display};
+
+use crate::{
+    solution::{Outcome, Solution},
+    ParseError, Source,
+};
+
+mod assembler;
+mod debugger;
+mod disassembler;
+mod instruction;
+mod quine;
+mod trace;
+mod word;
+
+pub fn solution_part1(input: &str) -> Result<String, PuzzleError> {
+    let mut program: Program = Program::from_input(input)?;
+    Ok(program.execute()?)
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let mut program = Program::from_input(input);
-    program.find_a_register_to_output_copy()
+pub fn solution_part2(input: &str) -> Result<u64, PuzzleError> {
+    let program: Program = Program::from_input(input)?;
+    Ok(program.lowest_quine()?)
 }
 
-struct Program {
-    registers: [usize; 3],
+pub fn disassemble(input: &str) -> Result<Disassembly, ParseError> {
+    let program: Program = Program::from_input(input)?;
+    Ok(program.disassemble())
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Parsed<'a> = Program;
+    type Params = ();
+    type Answer1 = Outcome<String, VmError>;
+    type Answer2 = Outcome<u64, QuineError>;
+
+    fn parse(input: &str) -> Result<Program, ParseError> {
+        Program::from_input(input)
+    }
+
+    fn part1(mut program: Program, _: &()) -> Outcome<String, VmError> {
+        Outcome(program.execute())
+    }
+
+    fn part2(program: Program, _: &()) -> Outcome<u64, QuineError> {
+        Outcome(program.lowest_quine())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VmError {
+    /// Programs are made of 3-bit numbers only.
+    InvalidNumber {
+        address: usize,
+        value: u8,
+    },
+    /// Combo operand 7 doesn't appear in valid programs.
+    ReservedOperand {
+        address: usize,
+    },
+    StepLimit(usize),
+    OutputLimit(usize),
+    /// The program came back to the same instruction with the same registers, so it never halts.
+    Loop {
+        address: usize,
+    },
+}
+
+impl Display for VmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VmError::InvalidNumber { address, value } => {
+                write!(f, "{} at address {} isn't a 3-bit number", value, address)
+            }
+            VmError::ReservedOperand { address } => {
+                write!(f, "reserved combo operand 7 at address {}", address)
+            }
+            VmError::StepLimit(limit) => write!(f, "still running after {} steps", limit),
+            VmError::OutputLimit(limit) => write!(f, "output longer than {} numbers", limit),
+            VmError::Loop { address } => write!(f, "infinite loop at address {}", address),
+        }
+    }
+}
+
+impl std::error::Error for VmError {}
+
+/// Why [`solution_part1`] or [`solution_part2`] has no answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleError {
+    Parse(ParseError),
+    Vm(VmError),
+    Quine(QuineError),
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PuzzleError::Parse(error) => write!(f, "invalid input at {}", error),
+            PuzzleError::Vm(error) => write!(f, "{}", error),
+            PuzzleError::Quine(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for PuzzleError {}
+
+impl From<ParseError> for PuzzleError {
+    fn from(error: ParseError) -> Self {
+        PuzzleError::Parse(error)
+    }
+}
+
+impl From<VmError> for PuzzleError {
+    fn from(error: VmError) -> Self {
+        PuzzleError::Vm(error)
+    }
+}
+
+impl From<QuineError> for PuzzleError {
+    fn from(error: QuineError) -> Self {
+        PuzzleError::Quine(error)
+    }
+}
+
+/// Bounds on a run, none by default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Limits {
+    pub steps: Option<usize>,
+    pub output: Option<usize>,
+    /// Remembers every state the program goes through, to fail as soon as one repeats.
+    pub detect_loops: bool,
+}
+
+impl Limits {
+    pub fn with_steps(self, steps: usize) -> Self {
+        Self {
+            steps: Some(steps),
+            ..self
+        }
+    }
+
+    pub fn with_output(self, output: usize) -> Self {
+        Self {
+            output: Some(output),
+            ..self
+        }
+    }
+
+    pub fn with_loop_detection(self) -> Self {
+        Self {
+            detect_loops: true,
+            ..self
+        }
+    }
+}
+
+/// A program along with its registers, `u64` by default. Wider registers give room to longer
+/// programs, up to [`BigUint`](crate::big::BigUint) for registers without a width.
+pub struct Program<W: Word = u64> {
+    registers: [W; 3],
     instructions: Vec<u8>,
     instruction_pointer: usize,
 }
 
-impl Program {
-    fn from_input(input: &str) -> Self {
-        let mut registers = [usize::MAX; 3];
-        let mut lines = input.lines();
-        registers[0] = parse_input_with_prefix("Register A: ", &mut lines)
-            .parse::<usize>()
-            .unwrap();
-        registers[1] = parse_input_with_prefix("Register B: ", &mut lines)
-            .parse::<usize>()
-            .unwrap();
-        registers[2] = parse_input_with_prefix("Register C: ", &mut lines)
-            .parse::<usize>()
-            .unwrap();
-        lines.next().unwrap();
-        let instructions = parse_input_with_prefix("Program: ", &mut lines)
-            .split(',')
-            .map(|o| o.parse::<u8>().unwrap())
-            .collect::<Vec<u8>>();
+impl<W: Word> Program<W> {
+    /// A program for the given registers `A`, `B` and `C`, `instructions` being 3-bit numbers
+    /// such as the ones [`assemble`] gives.
+    pub fn new(registers: [W; 3], instructions: Vec<u8>) -> Result<Self, VmError> {
+        if let Some((address, &value)) = instructions
+            .iter()
+            .enumerate()
+            .find(|(_, instruction)| **instruction > 7)
+        {
+            return Err(VmError::InvalidNumber { address, value });
+        }
 
-        Self {
+        Ok(Self {
             registers,
             instructions,
             instruction_pointer: 0,
-        }
+        })
     }
 
-    fn execute(&mut self) -> String {
-        self.execute_bit_output()
-            .iter()
-            .map(u8::to_string)
-            .collect::<Vec<_>>()
-            .join(",")
+    pub fn registers(&self) -> &[W; 3] {
+        &self.registers
     }
 
-    fn execute_bit_output(&mut self) -> Vec<u8> {
-        let mut output = Vec::<u8>::new();
-
-        while let Some(maybe_output) = self.execute_instruction() {
-            if let Some(out) = maybe_output {
-                output.push(out);
-            }
+    /// The same program back at its start, with `a` in A.
+    pub fn with_a(&self, a: W) -> Self {
+        Self {
+            registers: [a, self.registers[1].clone(), self.registers[2].clone()],
+            instructions: self.instructions.clone(),
+            instruction_pointer: 0,
         }
+    }
 
-        output
+    /// Runs from the current state until the program halts, giving its output.
+    pub fn run(&mut self, limits: &Limits) -> Result<Vec<u8>, VmError> {
+        self.run_with(limits, None)
     }
 
-    fn execute_instruction(&mut self) -> Option<Option<u8>> {
-        if let (Some(op_code), operand_option) = (
-            self.instructions.get(self.instruction_pointer),
-            self.instructions.get(self.instruction_pointer + 1),
-        ) {
-            self.instruction_pointer += 2;
+    /// Like [`Program::run`], adding each step that ran to `trace`, up to the failing one.
+    pub fn run_traced(
+        &mut self,
+        limits: &Limits,
+        trace: &mut Trace<W>,
+    ) -> Result<Vec<u8>, VmError> {
+        self.run_with(limits, Some(trace))
+    }
 
-            match op_code {
-                0 => {
-                    self.registers[0] = self.registers[0]
-                        / 2usize.pow(self.combo_operand_value(*operand_option.unwrap()) as u32)
-                }
-                1 => {
-                    self.registers[1] =
-                        self.registers[1] ^ self.literal_operand_value(*operand_option.unwrap())
-                }
-                2 => self.registers[1] = self.combo_operand_value(*operand_option.unwrap()) % 8,
-                3 => {
-                    if self.registers[0] != 0 {
-                        self.instruction_pointer =
-                            self.literal_operand_value(*operand_option.unwrap());
+    fn run_with(
+        &mut self,
+        limits: &Limits,
+        mut trace: Option<&mut Trace<W>>,
+    ) -> Result<Vec<u8>, VmError> {
+        let mut output = Vec::<u8>::new();
+        let mut seen = HashSet::new();
+        let mut steps = 0;
+
+        loop {
+            if limits.detect_loops
+                && !seen.insert((self.instruction_pointer, self.registers.clone()))
+            {
+                return Err(VmError::Loop {
+                    address: self.instruction_pointer,
+                });
+            }
+
+            if limits.steps.is_some_and(|limit| steps == limit) && !self.is_halted() {
+                return Err(VmError::StepLimit(steps));
+            }
+            steps += 1;
+
+            let ip = self.instruction_pointer;
+            let before = trace.is_some().then(|| self.registers.clone());
+            let executed = self.execute_instruction()?;
+            if let (Some(trace), Some(before), Some(out)) = (trace.as_deref_mut(), before, executed)
+            {
+                trace.push(TraceRow {
+                    ip,
+                    opcode: self.instructions[ip],
+                    operand: self.instructions[ip + 1],
+                    before,
+                    after: self.registers.clone(),
+                    output: out,
+                });
+            }
+
+            match executed {
+                None => return Ok(output),
+                Some(Some(out)) => {
+                    if limits.output.is_some_and(|limit| output.len() == limit) {
+                        return Err(VmError::OutputLimit(output.len()));
                     }
+                    output.push(out);
                 }
-                4 => self.registers[1] = self.registers[1] ^ self.registers[2],
-                5 => {
-                    return Some(Some(
-                        (self.combo_operand_value(*operand_option.unwrap()) % 8) as u8,
-                    ))
-                }
-                6 => {
-                    self.registers[1] = self.registers[0]
-                        / 2usize.pow(self.combo_operand_value(*operand_option.unwrap()) as u32)
-                }
-                7 => {
-                    self.registers[2] = self.registers[0]
-                        / 2usize.pow(self.combo_operand_value(*operand_option.unwrap()) as u32)
-                }
-                _ => panic!("Invalid op code {}", op_code),
+                Some(None) => {}
             }
+        }
+    }
 
-            Some(None)
-        } else {
-            None
+    /// Parses the puzzle input format, the registers having to fit in `W`.
+    pub fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+        let mut lines = input.lines();
+        let registers = [
+            source.number::<W>(parse_input_with_prefix(
+                &source,
+                "Register A: ",
+                &mut lines,
+            )?)?,
+            source.number::<W>(parse_input_with_prefix(
+                &source,
+                "Register B: ",
+                &mut lines,
+            )?)?,
+            source.number::<W>(parse_input_with_prefix(
+                &source,
+                "Register C: ",
+                &mut lines,
+            )?)?,
+        ];
+
+        let separator = source.next_line(&mut lines, "an empty line")?;
+        if !separator.is_empty() {
+            return Err(source.error(separator, "an empty line"));
         }
+
+        let instructions = parse_input_with_prefix(&source, "Program: ", &mut lines)?
+            .split(',')
+            .map(|o| match source.number::<u8>(o)? {
+                instruction @ 0..=7 => Ok(instruction),
+                _ => Err(source.error(o, "a 3-bit number")),
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        Ok(Self {
+            registers,
+            instructions,
+            instruction_pointer: 0,
+        })
     }
 
-    fn find_a_register_to_output_copy(&mut self) -> usize {
-        let current_bit = self.instructions.len() - 1;
+    fn execute(&mut self) -> Result<String, VmError> {
+        Ok(self
+            .run(&Limits::default())?
+            .iter()
+            .map(u8::to_string)
+            .collect::<Vec<_>>()
+            .join(","))
+    }
 
-        fn recursive(program: &mut Program, mut a: usize, current_bit: usize) -> Option<usize> {
-            for _ in 0..8 {
-                program.registers = [a, 0, 0];
-                program.instruction_pointer = 0;
+    fn is_halted(&self) -> bool {
+        self.current_instruction().is_none()
+    }
 
-                let output = program.execute_bit_output();
+    /// `None` once the program halted.
+    fn current_instruction(&self) -> Option<Instruction> {
+        let opcode = *self.instructions.get(self.instruction_pointer)?;
+        let operand = *self.instructions.get(self.instruction_pointer + 1)?;
 
-                if output.len() >= current_bit
-                    && output[current_bit] == program.instructions[current_bit]
-                {
-                    if current_bit == 0 {
-                        return Some(a);
-                    } else if let Some(register_a) = recursive(program, a, current_bit - 1) {
-                        return Some(register_a);
-                    }
-                }
+        Some(Instruction::decode(opcode, operand).expect("programs to hold 3-bit numbers"))
+    }
 
-                a += 8usize.pow(current_bit as u32);
-            }
+    /// `None` once the program halted, else the output of the instruction if it had one. The
+    /// state is left as it was on errors.
+    fn execute_instruction(&mut self) -> Result<Option<Option<u8>>, VmError> {
+        let Some(instruction) = self.current_instruction() else {
+            return Ok(None);
+        };
 
-            None
+        let mut next_instruction = self.instruction_pointer + 2;
+        let mut output = None;
+
+        match instruction {
+            Instruction::Adv(combo) => self.registers[0] = self.divide_a(combo)?,
+            Instruction::Bxl(literal) => {
+                self.registers[1] = self.registers[1].xor(&W::from_u64(literal as u64))
+            }
+            Instruction::Bst(combo) => {
+                let value = self.combo_operand_value(combo)?.low_bits();
+                self.registers[1] = W::from_u64(value as u64);
+            }
+            Instruction::Jnz(literal) => {
+                if !self.registers[0].is_zero() {
+                    next_instruction = literal as usize;
+                }
+            }
+            Instruction::Bxc(_) => self.registers[1] = self.registers[1].xor(&self.registers[2]),
+            Instruction::Out(combo) => output = Some(self.combo_operand_value(combo)?.low_bits()),
+            Instruction::Bdv(combo) => self.registers[1] = self.divide_a(combo)?,
+            Instruction::Cdv(combo) => self.registers[2] = self.divide_a(combo)?,
         }
 
-        recursive(self, 0, current_bit).expect("a solution to exists")
+        self.instruction_pointer = next_instruction;
+        Ok(Some(output))
     }
 
-    fn combo_operand_value(&self, operand: u8) -> usize {
-        match operand {
-            0..=3 => operand as usize,
-            4 => self.registers[0],
-            5 => self.registers[1],
-            6 => self.registers[2],
-            _ => panic!("Invalid operand value: {}", operand),
+    /// `A / 2^combo` as a right shift, which is 0 once the power is past the bits of A.
+    fn divide_a(&self, combo: Combo) -> Result<W, VmError> {
+        let power = self.combo_operand_value(combo)?;
+
+        Ok(self.registers[0].shr(power.to_u64().unwrap_or(u64::MAX)))
+    }
+
+    fn combo_operand_value(&self, combo: Combo) -> Result<W, VmError> {
+        match combo {
+            Combo::Literal(literal) => Ok(W::from_u64(literal as u64)),
+            Combo::A => Ok(self.registers[0].clone()),
+            Combo::B => Ok(self.registers[1].clone()),
+            Combo::C => Ok(self.registers[2].clone()),
+            Combo::Reserved => Err(VmError::ReservedOperand {
+                address: self.instruction_pointer,
+            }),
         }
     }
+}
 
-    fn literal_operand_value(&self, operand: u8) -> usize {
-        match operand {
-            0..=7 => operand as usize,
-            _ => panic!("Invalid operand value: {}", operand),
+/// In the puzzle input format, that [`Day17`] parses back.
+impl<W: Word> Display for Program<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, value) in ["A", "B", "C"].iter().zip(&self.registers) {
+            writeln!(f, "Register {}: {}", name, value)?;
         }
+
+        let instructions = self
+            .instructions
+            .iter()
+            .map(u8::to_string)
+            .collect::<Vec<_>>();
+        write!(f, "\nProgram: {}", instructions.join(","))
     }
 }
 
 fn parse_input_with_prefix<'a, I: Iterator<Item = &'a str>>(
+    source: &Source,
     prefix: &str,
     lines: &mut I,
-) -> &'a str {
-    lines.next().unwrap().strip_prefix(prefix).unwrap()
+) -> Result<&'a str, ParseError> {
+    let line = source.next_line(lines, &format!("`{}`", prefix))?;
+    source.strip_prefix(line, prefix)
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
     use super::*;
+    use crate::{answers::real_answer, big::BigUint, input::real_input, solution::Part};
 
     const PRE_EXAMPLE1: &str = "Register A: 0
 Register B: 0
@@ -214,64 +470,512 @@ Register C: 0
 
 Program: 0,3,5,4,3,0";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_pre_example1() {
-        let mut program = Program::from_input(PRE_EXAMPLE1);
-        program.execute();
+        let mut program: Program = Program::from_input(PRE_EXAMPLE1).unwrap();
+        program.execute().unwrap();
 
         assert_eq!(program.registers[1], 1);
     }
 
     #[test]
     fn test_part1_pre_example2() {
-        assert_eq!(solution_part1(PRE_EXAMPLE2), "0,1,2");
+        assert_eq!(solution_part1(PRE_EXAMPLE2).as_deref(), Ok("0,1,2"));
     }
 
     #[test]
     fn test_part1_pre_example3() {
-        assert_eq!(solution_part1(PRE_EXAMPLE3), "4,2,5,6,7,7,7,7,3,1,0");
+        assert_eq!(
+            solution_part1(PRE_EXAMPLE3).as_deref(),
+            Ok("4,2,5,6,7,7,7,7,3,1,0")
+        );
     }
 
     #[test]
     fn test_part1_pre_example4() {
-        let mut program = Program::from_input(PRE_EXAMPLE4);
-        program.execute();
+        let mut program: Program = Program::from_input(PRE_EXAMPLE4).unwrap();
+        program.execute().unwrap();
 
         assert_eq!(program.registers[1], 26);
     }
 
     #[test]
     fn test_part1_pre_example5() {
-        let mut program = Program::from_input(PRE_EXAMPLE5);
-        program.execute();
+        let mut program: Program = Program::from_input(PRE_EXAMPLE5).unwrap();
+        program.execute().unwrap();
 
         assert_eq!(program.registers[1], 44354);
     }
 
+    #[test]
+    fn test_part1_errors() {
+        let input = "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 5,7";
+        assert_eq!(
+            solution_part1(input),
+            Err(PuzzleError::Vm(VmError::ReservedOperand { address: 0 }))
+        );
+        assert_eq!(
+            Day17::part1(Day17::parse(input).unwrap(), &()).to_string(),
+            "no answer: reserved combo operand 7 at address 0"
+        );
+        assert!(matches!(
+            solution_part1("Program: 5,7"),
+            Err(PuzzleError::Parse(_))
+        ));
+    }
+
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), "4,6,3,5,6,3,5,2,1,0");
+        assert_eq!(
+            solution_part1(EXAMPLE).as_deref(),
+            Ok("4,6,3,5,6,3,5,2,1,0")
+        );
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), "1,7,6,5,1,0,5,0,7");
+        let expected = real_answer!(17, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(17)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_reverse_example() {
-        assert_eq!(solution_part1(REVERSE_EXAMPLE2), "0,3,5,4,3,0");
+        assert_eq!(
+            solution_part1(REVERSE_EXAMPLE2).as_deref(),
+            Ok("0,3,5,4,3,0")
+        );
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let disassembly = disassemble(EXAMPLE2).unwrap();
+
+        assert_eq!(
+            disassembly.instructions(),
+            &[
+                (0, Instruction::Adv(Combo::Literal(3))),
+                (2, Instruction::Out(Combo::A)),
+                (4, Instruction::Jnz(0)),
+            ]
+        );
+        assert_eq!(disassembly.jump_targets(), BTreeSet::from([0]));
+        assert_eq!(
+            disassembly.to_string(),
+            "L0:
+    adv 3   ; 0: 0,3
+    out A   ; 2: 5,4
+    jnz L0  ; 4: 3,0
+"
+        );
+    }
+
+    #[test]
+    fn test_disassemble_odd_programs() {
+        let disassembly = disassemble(
+            "Register A: 0
+Register B: 0
+Register C: 0
+
+Program: 4,1,3,3,7",
+        )
+        .unwrap();
+
+        assert_eq!(disassembly.dangling(), Some(7));
+        assert_eq!(
+            disassembly.to_string(),
+            "    bxc 1   ; 0: 4,1
+    jnz 3   ; 2: 3,3
+    ; 4: 7 without an operand
+"
+        );
+    }
+
+    #[test]
+    fn test_assemble() {
+        let code = assemble(
+            "; prints A in octal, least significant digit first
+loop:
+    adv 3
+    out A     ; the next digit
+    jnz loop",
+        )
+        .unwrap();
+
+        assert_eq!(code, vec![0, 3, 5, 4, 3, 0]);
+        assert_eq!(
+            Program::<u64>::new([2024, 0, 0], code).unwrap().to_string(),
+            EXAMPLE2
+        );
+
+        let disassembly = disassemble(EXAMPLE).unwrap().to_string();
+        assert_eq!(assemble(&disassembly), Ok(vec![0, 1, 5, 4, 3, 0]));
+        assert_eq!(assemble("bxc 3\nstart: jnz 5"), Ok(vec![4, 3, 3, 5]));
+    }
+
+    #[test]
+    fn test_assemble_errors() {
+        let error = |source| assemble(source).unwrap_err();
+
+        assert_eq!(
+            error("adv 3\nout 4"),
+            AssembleError {
+                line: 2,
+                kind: AssembleErrorKind::OperandOutOfRange {
+                    operand: "4".to_string(),
+                    allowed: "0 to 3, A, B or C",
+                },
+            }
+        );
+        assert_eq!(
+            error("bxl 8").kind,
+            AssembleErrorKind::OperandOutOfRange {
+                operand: "8".to_string(),
+                allowed: "0 to 7",
+            }
+        );
+        assert_eq!(
+            error("jnz nowhere").kind,
+            AssembleErrorKind::UnknownLabel("nowhere".to_string())
+        );
+        assert_eq!(
+            error("out A\nout A\nout A\nout A\nend: jnz end").kind,
+            AssembleErrorKind::LabelOutOfRange {
+                label: "end".to_string(),
+                address: 8,
+            }
+        );
+        assert_eq!(
+            error("a:\na: mul 2").kind,
+            AssembleErrorKind::DuplicateLabel("a".to_string())
+        );
+        assert_eq!(
+            error("mul 2").kind,
+            AssembleErrorKind::UnknownMnemonic("mul".to_string())
+        );
+        assert_eq!(error("bst").kind, AssembleErrorKind::MissingOperand("bst"));
+        assert_eq!(
+            error("bst A B").kind,
+            AssembleErrorKind::UnexpectedToken("B".to_string())
+        );
+    }
+
+    #[test]
+    fn test_debugger() {
+        let mut debugger = Debugger::<u64>::new(Program::from_input(EXAMPLE).unwrap());
+        debugger.add_breakpoint(4);
+        debugger.watch(Register::A);
+
+        assert_eq!(
+            debugger.step(),
+            vec![
+                Event::Stepped {
+                    address: 0,
+                    instruction: Instruction::Adv(Combo::Literal(1)),
+                },
+                Event::Watchpoint {
+                    register: Register::A,
+                    old: 729,
+                    new: 364,
+                },
+            ]
+        );
+        assert_eq!(
+            debugger.resume(),
+            vec![
+                Event::Stepped {
+                    address: 2,
+                    instruction: Instruction::Out(Combo::A),
+                },
+                Event::Output(4),
+                Event::Breakpoint(4),
+            ]
+        );
+
+        debugger.unwatch(Register::A);
+        debugger.remove_breakpoint(4);
+        assert_eq!(debugger.run_to_output().last(), Some(&Event::Output(6)));
+        assert_eq!(debugger.output(), &[4, 6]);
+
+        assert_eq!(debugger.resume().last(), Some(&Event::Halted));
+        assert!(debugger.is_halted());
+        assert_eq!(debugger.output(), &[4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+        assert_eq!(debugger.step(), vec![Event::Halted]);
+    }
+
+    #[test]
+    fn test_repl() {
+        let mut output = vec![];
+        repl(
+            Program::<u64>::from_input(EXAMPLE).unwrap(),
+            "break 2\ncontinue\nout\nregisters\nprint\nquit\nstep".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.ends_with(
+            "breakpoint at 2 set
+   0  adv 1
+breakpoint at 2
+   2  out A
+      out: 4
+A=364 B=0 C=0 ip=4
+4
+"
+        ));
+    }
+
+    #[test]
+    fn test_vm_errors() {
+        let run = |code: &str, a: u64, limits: &Limits| {
+            Program::<u64>::new([a, 0, 0], assemble(code).unwrap())
+                .unwrap()
+                .run(limits)
+        };
+
+        // `out 7`, that the assembler refuses
+        assert_eq!(
+            Program::<u64>::new([8, 0, 0], vec![0, 1, 5, 7])
+                .unwrap()
+                .run(&Limits::default()),
+            Err(VmError::ReservedOperand { address: 2 })
+        );
+        assert_eq!(
+            run(
+                "loop: out A\njnz loop",
+                1,
+                &Limits::default().with_steps(10)
+            ),
+            Err(VmError::StepLimit(10))
+        );
+        assert_eq!(
+            run(
+                "loop: out A\njnz loop",
+                1,
+                &Limits::default().with_output(3)
+            ),
+            Err(VmError::OutputLimit(3))
+        );
+        assert_eq!(
+            run(
+                "bxl 1\nloop: bxc\njnz loop",
+                1,
+                &Limits::default().with_loop_detection()
+            ),
+            Err(VmError::Loop { address: 2 })
+        );
+        assert_eq!(
+            run("cdv A\nout C", 70, &Limits::default().with_steps(2)),
+            Ok(vec![0])
+        );
+        assert!(matches!(
+            Program::<u64>::new([0, 0, 0], vec![0, 8]),
+            Err(VmError::InvalidNumber {
+                address: 1,
+                value: 8
+            })
+        ));
+
+        let mut debugger = Debugger::new(Program::<u64>::new([0, 0, 0], vec![5, 7]).unwrap());
+        assert_eq!(
+            debugger.resume(),
+            vec![Event::Error(VmError::ReservedOperand { address: 0 })]
+        );
+        assert!(!debugger.is_halted());
+    }
+
+    #[test]
+    fn test_quines() {
+        let program = |source| Program::<u64>::new([0, 0, 0], assemble(source).unwrap()).unwrap();
+        let is_quine = |program: &Program, a| {
+            let mut program = Program::<u64>::new([a, 0, 0], program.instructions.clone()).unwrap();
+            program.run(&Limits::default()) == Ok(program.instructions.clone())
+        };
+
+        // 4 bits per pass, out of two shifts
+        let wide = program("l: bst A\nbxl 5\nout B\nadv 2\nadv 2\njnz l");
+        let quines = wide.quines().unwrap().take(3).collect::<Vec<_>>();
+        assert_eq!(wide.lowest_quine(), Ok(quines[0]));
+        assert!(quines.is_sorted());
+        assert!(quines.iter().all(|a| is_quine(&wide, *a)));
+
+        // two different outputs per pass
+        let alternating =
+            program("l: bst A\nbxl 5\nout B\nadv 3\nbst A\nbxl 6\nout B\nadv 3\njnz l");
+        let lowest = alternating.lowest_quine().unwrap();
+        assert!(is_quine(&alternating, lowest));
+    }
+
+    #[test]
+    fn test_quine_errors() {
+        let program = |source| Program::<u64>::new([0, 0, 0], assemble(source).unwrap()).unwrap();
+
+        assert_eq!(
+            program("bst A\nbxl 1\nout B\nadv 2\njnz 0").lowest_quine(),
+            Err(QuineError::NoSolution)
+        );
+        assert_eq!(program("out A").lowest_quine(), Err(QuineError::NoLoop));
+        assert_eq!(
+            program("l: out A\nadv B\njnz l").lowest_quine(),
+            Err(QuineError::RegisterShift { address: 2 })
+        );
+    }
+
+    #[test]
+    fn test_words() {
+        let two_to_the = |power| Word::shl(&BigUint::from_u64(1), power).unwrap();
+
+        assert_eq!(Word::bit_length(&two_to_the(200)), 201);
+        assert_eq!(Word::shr(&two_to_the(200), 197), BigUint::from_u64(8));
+        assert_eq!(Word::to_u64(&two_to_the(130)), None);
+        assert_eq!(BigUint::from_u64(13).low_bits(), 5);
+        assert_eq!(u64::MAX.shl(1), None);
+        assert_eq!(1u128.shl(127), Some(1 << 127));
+    }
+
+    #[test]
+    fn test_wide_registers() {
+        // shifts past 64 bits
+        let mut program = Program::<u128>::new(
+            [1 << 100 | 5 << 97, 97, 0],
+            assemble("cdv B\nout C\nadv A\nout A").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(program.run(&Limits::default()), Ok(vec![5, 0]));
+        assert_eq!(program.registers(), &[0, 97, 13]);
+
+        let input = "Register A: 1606938044258990275541962092341162602522202993782792835301376
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0";
+        let program = Program::<BigUint>::from_input(input).unwrap();
+        assert_eq!(program.to_string(), input);
+        assert!(Program::<u128>::from_input(input).is_err());
+
+        // a quine of 24 numbers needs 72 bits
+        let source = format!("l: adv 3\nout A\n{}jnz l", "bxl 0\n".repeat(9));
+        let code = assemble(&source).unwrap();
+        assert_eq!(
+            Program::<u64>::new([0, 0, 0], code.clone())
+                .unwrap()
+                .lowest_quine(),
+            Err(QuineError::NoSolution)
+        );
+
+        let lowest = Program::<u128>::new([0, 0, 0], code.clone())
+            .unwrap()
+            .lowest_quine()
+            .unwrap();
+        assert!(lowest > u64::MAX as u128);
+        let mut program = Program::new([lowest, 0, 0], code.clone()).unwrap();
+        assert_eq!(program.run(&Limits::default()), Ok(code.clone()));
+
+        let big = Program::new(
+            [BigUint::default(), BigUint::default(), BigUint::default()],
+            code,
+        )
+        .unwrap()
+        .lowest_quine()
+        .unwrap();
+        assert_eq!(big.to_string(), lowest.to_string());
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut program: Program = Program::from_input(EXAMPLE2).unwrap().with_a(17);
+        let mut trace = Trace::new();
+
+        assert_eq!(
+            program.run_traced(&Limits::default(), &mut trace),
+            Ok(vec![2, 0])
+        );
+        assert_eq!(trace.rows().len(), 6);
+        assert_eq!(
+            trace.rows()[1],
+            TraceRow {
+                ip: 2,
+                opcode: 5,
+                operand: 4,
+                before: [2, 0, 0],
+                after: [2, 0, 0],
+                output: Some(2),
+            }
+        );
+
+        let mut csv = vec![];
+        trace.write(TraceFormat::Csv, &mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "ip,opcode,operand,a_before,b_before,c_before,a_after,b_after,c_after,output
+0,0,3,17,0,0,2,0,0,
+2,5,4,2,0,0,2,0,0,2
+4,3,0,2,0,0,2,0,0,
+0,0,3,2,0,0,0,0,0,
+2,5,4,0,0,0,0,0,0,0
+4,3,0,0,0,0,0,0,0,
+"
+        );
+
+        let mut json = vec![];
+        trace.write(TraceFormat::JsonLines, &mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert_eq!(
+            json.lines().nth(1),
+            Some(r#"{"ip":2,"opcode":5,"operand":4,"before":[2,0,0],"after":[2,0,0],"output":2}"#)
+        );
+
+        // the steps up to the failure are kept
+        let mut program = Program::<u64>::new([0, 0, 0], vec![1, 2, 5, 7]).unwrap();
+        let mut trace = Trace::new();
+        assert_eq!(
+            program.run_traced(&Limits::default(), &mut trace),
+            Err(VmError::ReservedOperand { address: 2 })
+        );
+        assert_eq!(trace.rows().len(), 1);
+        assert_eq!(trace.rows()[0].after, [0, 2, 0]);
+        assert_eq!("json".parse::<TraceFormat>().ok(), None);
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE2), 117440);
+        assert_eq!(solution_part2(EXAMPLE2), Ok(117440));
+    }
+
+    #[test]
+    fn test_part2_errors() {
+        let input = |program| {
+            format!(
+                "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: {}",
+                program
+            )
+        };
+
+        assert_eq!(
+            solution_part2(&input("0,1,5,4,3,0")),
+            Err(PuzzleError::Quine(QuineError::NoSolution))
+        );
+        assert_eq!(
+            solution_part2(&input("2,4,1,1,5,5,3,0")),
+            Err(PuzzleError::Quine(QuineError::IrregularShift))
+        );
+        assert_eq!(
+            Day17::part2(Day17::parse(&input("0,1,5,4,3,0")).unwrap(), &()).to_string(),
+            "no answer: no value of A makes the program output itself"
+        );
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2_input() {
-        assert_eq!(solution_part2(INPUT), 236555995274861);
+        let expected = real_answer!(17, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(17)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
@@ -0,0 +1,186 @@
+use std::fmt::Display;
+
+use super::{
+    instruction::{Combo, Instruction},
+    Limits, Program, VmError, Word,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuineError {
+    /// The search needs every `adv` to shift A by a constant.
+    RegisterShift {
+        address: usize,
+    },
+    /// The program outputs at most once, so its output can't grow with A.
+    NoLoop,
+    /// The loop doesn't shift A, or not by the same amounts on every pass.
+    IrregularShift,
+    /// A pass consumes that many bits of A, too many to try every value of them.
+    WidePass(u64),
+    Vm(VmError),
+    NoSolution,
+}
+
+impl Display for QuineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuineError::RegisterShift { address } => {
+                write!(f, "`adv` at address {} shifts A by a register", address)
+            }
+            QuineError::NoLoop => write!(f, "the program doesn't output in a loop"),
+            QuineError::IrregularShift => write!(f, "the program doesn't shift A regularly"),
+            QuineError::WidePass(bits) => {
+                write!(f, "a pass consumes {} bits of A, too many to search", bits)
+            }
+            QuineError::Vm(error) => write!(f, "the program fails: {}", error),
+            QuineError::NoSolution => write!(f, "no value of A makes the program output itself"),
+        }
+    }
+}
+
+impl std::error::Error for QuineError {}
+
+/// Every value of A that makes the program output itself, in increasing order.
+///
+/// The program must consume A from its least significant bits, a fixed number of bits per pass
+/// through its loops (whatever their number and the shift of each), with the outputs of a pass
+/// only depending on what's left of A then. A is then built from its most significant bits, a
+/// pass at a time, keeping the candidates whose output is the end of the program. Candidates
+/// that don't fit in `W` are skipped.
+pub struct Quines<'a, W: Word = u64> {
+    program: &'a Program<W>,
+    bits_per_pass: u64,
+    /// Candidates whose output ends like the program, along with the next extension to try.
+    stack: Vec<(W, u64)>,
+    tried_zero: bool,
+}
+
+// how much of A is set to measure passes when registers have no width
+const UNBOUNDED_SAMPLE_BITS: u32 = 1024;
+
+impl<W: Word> Program<W> {
+    pub fn quines(&self) -> Result<Quines<'_, W>, QuineError> {
+        let bits_per_pass = self.bits_per_pass()?;
+        if bits_per_pass >= u64::BITS as u64 {
+            return Err(QuineError::WidePass(bits_per_pass));
+        }
+
+        Ok(Quines {
+            program: self,
+            bits_per_pass,
+            stack: vec![(W::from_u64(0), 1)],
+            tried_zero: false,
+        })
+    }
+
+    /// The smallest A that makes the program output itself.
+    pub fn lowest_quine(&self) -> Result<W, QuineError> {
+        self.quines()?.next().ok_or(QuineError::NoSolution)
+    }
+
+    /// How many bits of A a pass through the loops of the program consumes, found by running it
+    /// with every bit of A set and looking at what is left of A at each `out`. A pass is the
+    /// shortest sequence of outputs that repeats, with the same shifts between them.
+    fn bits_per_pass(&self) -> Result<u64, QuineError> {
+        if let Some((address, _)) = self
+            .disassemble()
+            .instructions()
+            .iter()
+            .find(|(_, i)| matches!(i, Instruction::Adv(Combo::A | Combo::B | Combo::C)))
+        {
+            return Err(QuineError::RegisterShift { address: *address });
+        }
+
+        let sample_bits = W::BITS.unwrap_or(UNBOUNDED_SAMPLE_BITS);
+        let every_bit = (0..sample_bits / u64::BITS).fold(W::from_u64(0), |a, _| {
+            a.shl(u64::BITS as u64)
+                .expect("the sample to fit in a register")
+                .or(&W::from_u64(u64::MAX))
+        });
+        let mut program = self.with_a(every_bit);
+        let mut outputs = vec![];
+        let mut steps = 0;
+
+        while let Some(instruction) = program.current_instruction() {
+            // once A is 0 what it lost isn't known anymore
+            if matches!(instruction, Instruction::Out(_)) && !program.registers[0].is_zero() {
+                let bits_left = program.registers[0].bit_length();
+                outputs.push((program.instruction_pointer, bits_left));
+            }
+
+            // a pass shifts A by at least a bit, or it never ends
+            steps += 1;
+            if steps > 1 << 20 {
+                return Err(QuineError::IrregularShift);
+            }
+            program.execute_instruction().map_err(QuineError::Vm)?;
+        }
+
+        let shifts = outputs
+            .windows(2)
+            .map(|pair| (pair[1].0, pair[0].1 - pair[1].1))
+            .collect::<Vec<_>>();
+        if shifts.is_empty() {
+            return Err(QuineError::NoLoop);
+        }
+
+        let period = (1..=shifts.len() / 2)
+            .find(|period| (*period..shifts.len()).all(|i| shifts[i] == shifts[i - period]))
+            .ok_or(QuineError::IrregularShift)?;
+        match shifts[..period].iter().map(|(_, shift)| shift).sum() {
+            0 => Err(QuineError::IrregularShift),
+            bits => Ok(bits),
+        }
+    }
+
+    /// The output for that A, `None` if longer than the program or failing.
+    fn output_with_a(&self, a: W) -> Option<Vec<u8>> {
+        let limits = Limits::default()
+            .with_output(self.instructions.len())
+            .with_loop_detection();
+
+        self.with_a(a).run(&limits).ok()
+    }
+}
+
+impl<W: Word> Iterator for Quines<'_, W> {
+    type Item = W;
+
+    fn next(&mut self) -> Option<W> {
+        let instructions = &self.program.instructions;
+
+        if !self.tried_zero {
+            self.tried_zero = true;
+            if self.program.output_with_a(W::from_u64(0)).as_ref() == Some(instructions) {
+                return Some(W::from_u64(0));
+            }
+        }
+
+        while let Some((prefix, extension)) = self.stack.pop() {
+            // past the last extension, or a pass more would push the top bits out of A
+            if extension >> self.bits_per_pass != 0 {
+                continue;
+            }
+            let Some(shifted) = prefix.shl(self.bits_per_pass) else {
+                continue;
+            };
+            self.stack.push((prefix, extension + 1));
+
+            let a = shifted.or(&W::from_u64(extension));
+
+            let Some(output) = self.program.output_with_a(a.clone()) else {
+                continue;
+            };
+            if !instructions.ends_with(&output) {
+                continue;
+            }
+
+            self.stack.push((a.clone(), 0));
+            if output.len() == instructions.len() {
+                return Some(a);
+            }
+        }
+
+        None
+    }
+}
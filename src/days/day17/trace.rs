@@ -0,0 +1,121 @@
+use std::{io::Write, str::FromStr};
+
+use super::Word;
+
+/// A step of a run: the instruction at `ip` and the registers around it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceRow<W: Word = u64> {
+    pub ip: usize,
+    pub opcode: u8,
+    pub operand: u8,
+    pub before: [W; 3],
+    pub after: [W; 3],
+    pub output: Option<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    Csv,
+    /// A JSON object per line.
+    JsonLines,
+}
+
+impl FromStr for TraceFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(TraceFormat::Csv),
+            "jsonl" => Ok(TraceFormat::JsonLines),
+            _ => Err(format!("trace format must be csv or jsonl, got {}", s)),
+        }
+    }
+}
+
+const CSV_HEADER: &str =
+    "ip,opcode,operand,a_before,b_before,c_before,a_after,b_after,c_after,output";
+
+/// Every step of a run, in order, that [`super::Program::run_traced`] records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace<W: Word = u64> {
+    rows: Vec<TraceRow<W>>,
+}
+
+impl<W: Word> Default for Trace<W> {
+    fn default() -> Self {
+        Self { rows: vec![] }
+    }
+}
+
+impl<W: Word> Trace<W> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rows(&self) -> &[TraceRow<W>] {
+        &self.rows
+    }
+
+    pub(super) fn push(&mut self, row: TraceRow<W>) {
+        self.rows.push(row);
+    }
+
+    pub fn write(&self, format: TraceFormat, output: impl Write) -> std::io::Result<()> {
+        match format {
+            TraceFormat::Csv => self.write_csv(output),
+            TraceFormat::JsonLines => self.write_json_lines(output),
+        }
+    }
+
+    /// With a header, the output being empty for steps without one.
+    pub fn write_csv(&self, mut output: impl Write) -> std::io::Result<()> {
+        writeln!(output, "{}", CSV_HEADER)?;
+
+        for row in &self.rows {
+            let [a, b, c] = &row.before;
+            let [new_a, new_b, new_c] = &row.after;
+            writeln!(
+                output,
+                "{},{},{},{},{},{},{},{},{},{}",
+                row.ip,
+                row.opcode,
+                row.operand,
+                a,
+                b,
+                c,
+                new_a,
+                new_b,
+                new_c,
+                row.output.map(|out| out.to_string()).unwrap_or_default()
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Registers are written as plain JSON numbers, whatever their size.
+    pub fn write_json_lines(&self, mut output: impl Write) -> std::io::Result<()> {
+        for row in &self.rows {
+            let [a, b, c] = &row.before;
+            let [new_a, new_b, new_c] = &row.after;
+            writeln!(
+                output,
+                r#"{{"ip":{},"opcode":{},"operand":{},"before":[{},{},{}],"after":[{},{},{}],"output":{}}}"#,
+                row.ip,
+                row.opcode,
+                row.operand,
+                a,
+                b,
+                c,
+                new_a,
+                new_b,
+                new_c,
+                row.output
+                    .map(|out| out.to_string())
+                    .unwrap_or_else(|| "null".to_string())
+            )?;
+        }
+
+        Ok(())
+    }
+}
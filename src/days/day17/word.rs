@@ -0,0 +1,123 @@
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    str::FromStr,
+};
+
+use crate::big::BigUint;
+
+/// The unsigned integers registers can hold, fixed-width or not.
+pub trait Word: Clone + Eq + Ord + Hash + Debug + Display + FromStr {
+    /// `None` for integers without a fixed width.
+    const BITS: Option<u32>;
+
+    fn from_u64(value: u64) -> Self;
+
+    /// `None` if it doesn't fit.
+    fn to_u64(&self) -> Option<u64>;
+
+    /// The number of bits needed to write it, 0 for 0.
+    fn bit_length(&self) -> u64;
+
+    fn xor(&self, other: &Self) -> Self;
+
+    fn or(&self, other: &Self) -> Self;
+
+    /// Divides by `2^bits`, which gives 0 once `bits` is past the most significant bit.
+    fn shr(&self, bits: u64) -> Self;
+
+    /// Multiplies by `2^bits`, `None` if bits are lost.
+    fn shl(&self, bits: u64) -> Option<Self>;
+
+    fn is_zero(&self) -> bool {
+        self.bit_length() == 0
+    }
+
+    /// The value modulo 8.
+    fn low_bits(&self) -> u8;
+}
+
+macro_rules! fixed_width_word {
+    ($($t:ty),*) => {$(
+        impl Word for $t {
+            const BITS: Option<u32> = Some(<$t>::BITS);
+
+            fn from_u64(value: u64) -> Self {
+                value as $t
+            }
+
+            fn to_u64(&self) -> Option<u64> {
+                u64::try_from(*self).ok()
+            }
+
+            fn bit_length(&self) -> u64 {
+                (<$t>::BITS - self.leading_zeros()) as u64
+            }
+
+            fn xor(&self, other: &Self) -> Self {
+                self ^ other
+            }
+
+            fn or(&self, other: &Self) -> Self {
+                self | other
+            }
+
+            fn shr(&self, bits: u64) -> Self {
+                u32::try_from(bits)
+                    .ok()
+                    .and_then(|bits| self.checked_shr(bits))
+                    .unwrap_or(0)
+            }
+
+            fn shl(&self, bits: u64) -> Option<Self> {
+                if self.leading_zeros() as u64 >= bits || *self == 0 {
+                    Some(self.checked_shl(bits as u32).unwrap_or(0))
+                } else {
+                    None
+                }
+            }
+
+            fn low_bits(&self) -> u8 {
+                (self % 8) as u8
+            }
+        }
+    )*};
+}
+
+fixed_width_word!(u64, u128);
+
+impl Word for BigUint {
+    const BITS: Option<u32> = None;
+
+    fn from_u64(value: u64) -> Self {
+        Self::from(value)
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        BigUint::to_u64(self)
+    }
+
+    fn bit_length(&self) -> u64 {
+        BigUint::bit_length(self) as u64
+    }
+
+    fn xor(&self, other: &Self) -> Self {
+        BigUint::xor(self, other)
+    }
+
+    fn or(&self, other: &Self) -> Self {
+        BigUint::or(self, other)
+    }
+
+    fn shr(&self, bits: u64) -> Self {
+        BigUint::shr(self, bits)
+    }
+
+    fn shl(&self, bits: u64) -> Option<Self> {
+        Some(BigUint::shl(self, bits))
+    }
+
+    fn low_bits(&self) -> u8 {
+        self.truncate(3).to_u64().unwrap_or(0) as u8
+    }
+}
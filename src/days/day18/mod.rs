@@ -1,58 +1,105 @@
 // https://adventofcode.com/2024/day/18
 
-use std::{collections::BTreeMap, usize};
-
-use crate::Position;
-
-pub fn solution_part1(input: &str, width: usize, height: usize, bytes_fallen: usize) -> usize {
-    let mut map = MemoryMap::from_input(input, width, height);
+use crate::{
+    search,
+    solution::{Args, Params, Solution},
+    Grid, ParseError, Position, Source,
+};
+
+pub fn solution_part1(
+    input: &str,
+    width: usize,
+    height: usize,
+    bytes_fallen: usize,
+) -> Result<usize, ParseError> {
+    let mut map = MemoryMap::from_input(input, width, height)?;
     map.nanoseconds_passed(bytes_fallen);
-    map.shortest_path_length(Position(0, 0), Position(width - 1, height - 1))
-        .expect("should have a path")
+    Ok(map
+        .shortest_path_length_to_exit()
+        .expect("should have a path"))
 }
 
-pub fn solution_part2(input: &str, width: usize, height: usize, bytes_fallen: usize) -> String {
-    let mut map = MemoryMap::from_input(input, width, height);
+pub fn solution_part2(
+    input: &str,
+    width: usize,
+    height: usize,
+    bytes_fallen: usize,
+) -> Result<String, ParseError> {
+    let mut map = MemoryMap::from_input(input, width, height)?;
     map.nanoseconds_passed(bytes_fallen);
+    Ok(map.first_byte_cutting_off_exit())
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Parsed<'a> = Vec<Position>;
+    type Params = MemorySpace;
+    type Answer1 = usize;
+    type Answer2 = String;
 
-    loop {
-        if !map.is_there_a_path(Position(0, 0), Position(width - 1, height - 1)) {
-            return format!(
-                "{},{}",
-                map.bytes_to_fall[map.time - 1].0,
-                map.bytes_to_fall[map.time - 1].1
-            );
+    fn parse(input: &str) -> Result<Vec<Position>, ParseError> {
+        bytes_to_fall_from_input(input)
+    }
+
+    fn part1(bytes_to_fall: Vec<Position>, space: &MemorySpace) -> usize {
+        let mut map = MemoryMap::new(bytes_to_fall, space.width, space.height);
+        map.nanoseconds_passed(space.bytes_fallen);
+        map.shortest_path_length_to_exit()
+            .expect("should have a path")
+    }
+
+    fn part2(bytes_to_fall: Vec<Position>, space: &MemorySpace) -> String {
+        let mut map = MemoryMap::new(bytes_to_fall, space.width, space.height);
+        map.nanoseconds_passed(space.bytes_fallen);
+        map.first_byte_cutting_off_exit()
+    }
+}
+
+pub struct MemorySpace {
+    pub width: usize,
+    pub height: usize,
+    pub bytes_fallen: usize,
+}
+
+impl Default for MemorySpace {
+    fn default() -> Self {
+        Self {
+            width: 71,
+            height: 71,
+            bytes_fallen: 1024,
         }
-        map.nanoseconds_passed(1);
+    }
+}
+
+impl Params for MemorySpace {
+    fn from_args(args: &mut Args) -> Result<Self, String> {
+        let default = Self::default();
+
+        Ok(Self {
+            width: args.next_or("width", default.width)?,
+            height: args.next_or("height", default.height)?,
+            bytes_fallen: args.next_or("bytes_fallen", default.bytes_fallen)?,
+        })
     }
 }
 
 struct MemoryMap {
-    width: usize,
-    height: usize,
-    bytes_corrupted: Vec<Vec<bool>>,
+    bytes_corrupted: Grid<bool>,
     bytes_to_fall: Vec<Position>,
     time: usize,
 }
 
 impl MemoryMap {
-    fn from_input(input: &str, width: usize, height: usize) -> Self {
-        let bytes_to_fall = input
-            .lines()
-            .map(|byte_to_fall| {
-                let (x, y) = byte_to_fall.split_once(',').unwrap();
-                Position(x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap())
-            })
-            .collect::<Vec<_>>();
-
-        let bytes = (0..height)
-            .map(|_| (0..width).map(|_| false).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
+    fn from_input(input: &str, width: usize, height: usize) -> Result<Self, ParseError> {
+        Ok(Self::new(bytes_to_fall_from_input(input)?, width, height))
+    }
 
+    fn new(bytes_to_fall: Vec<Position>, width: usize, height: usize) -> Self {
         Self {
-            width,
-            height,
-            bytes_corrupted: bytes,
+            bytes_corrupted: Grid::new(width, height, false),
             bytes_to_fall,
             time: 0,
         }
@@ -61,98 +108,86 @@ impl MemoryMap {
     fn nanoseconds_passed(&mut self, nanoseconds: usize) {
         for i in 0..nanoseconds {
             let pos = &self.bytes_to_fall[self.time + i];
-            self.bytes_corrupted[pos.1][pos.0] = true;
+            self.bytes_corrupted[pos] = true;
         }
 
         self.time += nanoseconds;
     }
 
-    fn shortest_path_length(&self, start: Position, end: Position) -> Option<usize> {
-        let mut paths = BTreeMap::<usize, Vec<Position>>::new();
-        paths.insert(0, vec![start]);
-
-        let mut visited = (0..self.height)
-            .map(|_| (0..self.width).map(|_| false).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        visited[0][0] = true;
-
-        while let Some((steps, positions)) = paths.pop_first() {
-            for position in positions {
-                let up = position.up();
-                let down = position.down_bounded(self.height);
-                let right = position.right_bounded(self.width);
-                let left = position.left();
-
-                for next_position in [up, down, right, left].into_iter().filter_map(|p| p) {
-                    if visited[next_position.1][next_position.0]
-                        || self.bytes_corrupted[next_position.1][next_position.0]
-                    {
-                        continue;
-                    }
-                    visited[next_position.1][next_position.0] = true;
-
-                    if next_position == end {
-                        return Some(steps + 1);
-                    }
-
-                    if let Some(paths_mut) = paths.get_mut(&(steps + 1)) {
-                        paths_mut.push(next_position);
-                    } else {
-                        paths.insert(steps + 1, vec![next_position]);
-                    }
-                }
+    fn exit(&self) -> Position {
+        Position(
+            self.bytes_corrupted.width() - 1,
+            self.bytes_corrupted.height() - 1,
+        )
+    }
+
+    fn shortest_path_length_to_exit(&self) -> Option<usize> {
+        self.shortest_path_length(Position(0, 0), self.exit())
+    }
+
+    fn first_byte_cutting_off_exit(&mut self) -> String {
+        loop {
+            if !self.is_there_a_path(Position(0, 0), self.exit()) {
+                return format!(
+                    "{},{}",
+                    self.bytes_to_fall[self.time - 1].0,
+                    self.bytes_to_fall[self.time - 1].1
+                );
             }
+            self.nanoseconds_passed(1);
         }
+    }
 
-        None
+    fn shortest_path_length(&self, start: Position, end: Position) -> Option<usize> {
+        search::bfs(
+            start,
+            |position| self.free_neighbours(position),
+            |p| *p == end,
+        )
+        .distance(&end)
     }
 
     fn is_there_a_path(&self, start: Position, end: Position) -> bool {
-        let mut paths = BTreeMap::<usize, Vec<Position>>::new();
-        paths.insert(0, vec![start]);
-
-        let mut visited = (0..self.height)
-            .map(|_| (0..self.width).map(|_| false).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        visited[0][0] = true;
-
-        while let Some((_, positions)) = paths.pop_first() {
-            for position in positions {
-                let up = position.up();
-                let down = position.down_bounded(self.height);
-                let right = position.right_bounded(self.width);
-                let left = position.left();
-
-                for next_position in [up, down, right, left].into_iter().filter_map(|p| p) {
-                    if visited[next_position.1][next_position.0]
-                        || self.bytes_corrupted[next_position.1][next_position.0]
-                    {
-                        continue;
-                    }
-                    visited[next_position.1][next_position.0] = true;
-
-                    if next_position == end {
-                        return true;
-                    }
-
-                    let key = (end.0 - next_position.0).pow(2) + (end.1 - next_position.1).pow(2);
-
-                    if let Some(paths_mut) = paths.get_mut(&key) {
-                        paths_mut.push(next_position);
-                    } else {
-                        paths.insert(key, vec![next_position]);
-                    }
-                }
-            }
-        }
+        search::astar(
+            [start],
+            |position| {
+                self.free_neighbours(position)
+                    .into_iter()
+                    .map(|next_position| (next_position, 1))
+            },
+            |position| end.0.abs_diff(position.0) + end.1.abs_diff(position.1),
+            |p| *p == end,
+        )
+        .goal()
+        .is_some()
+    }
 
-        false
+    fn free_neighbours(&self, position: &Position) -> Vec<Position> {
+        self.bytes_corrupted
+            .neighbours(position)
+            .filter(|next_position| !self.bytes_corrupted[next_position])
+            .collect()
     }
 }
 
+fn bytes_to_fall_from_input(input: &str) -> Result<Vec<Position>, ParseError> {
+    let source = Source::new(input);
+    input
+        .lines()
+        .map(|byte_to_fall| {
+            let (x, y) = source.split_once(byte_to_fall, ",")?;
+            Ok(Position(
+                source.number::<usize>(x)?,
+                source.number::<usize>(y)?,
+            ))
+        })
+        .collect::<Result<Vec<_>, _>>()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "5,4
 4,2
@@ -180,25 +215,33 @@ mod tests {
 1,6
 2,0";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE, 7, 7, 12), 22);
+        assert_eq!(solution_part1(EXAMPLE, 7, 7, 12), Ok(22));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT, 71, 71, 1024), 454);
+        let expected = real_answer!(18, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(18), 71, 71, 1024).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE, 7, 7, 12), "6,1");
+        assert_eq!(solution_part2(EXAMPLE, 7, 7, 12).as_deref(), Ok("6,1"));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT, 71, 71, 1024), "8,51");
+        let expected = real_answer!(18, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(18), 71, 71, 1024).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
@@ -2,47 +2,75 @@
 
 use std::collections::HashMap;
 
-pub fn solution_part1(input: &str) -> usize {
-    let patterns = OnsenBranding::from_input(input);
-    patterns.how_many_possible()
+use crate::{solution::Solution, ParseError, Source};
+
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let patterns = OnsenBranding::from_input(input)?;
+    Ok(patterns.how_many_possible())
+}
+
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let patterns = OnsenBranding::from_input(input)?;
+    Ok(patterns.how_many_different_ways_possible())
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let patterns = OnsenBranding::from_input(input);
-    patterns.how_many_different_ways_possible()
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Parsed<'a> = OnsenBranding;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<OnsenBranding, ParseError> {
+        OnsenBranding::from_input(input)
+    }
+
+    fn part1(patterns: OnsenBranding, _: &()) -> usize {
+        patterns.how_many_possible()
+    }
+
+    fn part2(patterns: OnsenBranding, _: &()) -> usize {
+        patterns.how_many_different_ways_possible()
+    }
 }
 
-struct OnsenBranding {
+pub struct OnsenBranding {
     available: Vec<Vec<char>>,
     patterns: Vec<Vec<char>>,
 }
 
 impl OnsenBranding {
-    fn from_input(input: &str) -> Self {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
         let mut lines = input.lines();
-        let available = lines
-            .next()
-            .unwrap()
+        let available = source
+            .next_line(&mut lines, "the available towel patterns")?
             .split(',')
             .map(|a| a.trim().chars().collect::<Vec<char>>())
             .collect::<Vec<_>>();
 
-        lines.next().unwrap();
+        let separator = source.next_line(&mut lines, "an empty line")?;
+        if !separator.is_empty() {
+            return Err(source.error(separator, "an empty line"));
+        }
 
         let patterns = lines
             .map(|a| a.trim().chars().collect::<Vec<char>>())
             .collect::<Vec<_>>();
 
-        Self {
+        Ok(Self {
             available,
             patterns,
-        }
+        })
     }
 
     fn how_many_possible(&self) -> usize {
         self.patterns
             .iter()
-            .filter(|p| how_many_possible_recursive(0, &p, &self.available))
+            .filter(|p| how_many_possible_recursive(0, p, &self.available))
             .count()
     }
 
@@ -51,12 +79,8 @@ impl OnsenBranding {
         let mut cache = HashMap::<Vec<char>, usize>::new();
 
         for pattern in self.patterns.iter() {
-            total += how_many_different_ways_possible_recursive(
-                0,
-                &pattern,
-                &self.available,
-                &mut cache,
-            );
+            total +=
+                how_many_different_ways_possible_recursive(0, pattern, &self.available, &mut cache);
         }
 
         total
@@ -69,8 +93,8 @@ fn how_many_possible_recursive(start: usize, pattern: &[char], available: &[Vec<
     }
 
     for a in available {
-        if does_match(a, &pattern, start)
-            && how_many_possible_recursive(start + a.len(), &pattern, &available)
+        if does_match(a, pattern, start)
+            && how_many_possible_recursive(start + a.len(), pattern, available)
         {
             return true;
         }
@@ -89,7 +113,7 @@ fn how_many_different_ways_possible_recursive(
         return 1;
     }
 
-    let current_pattern = pattern.iter().skip(start).map(|c| *c).collect::<Vec<_>>();
+    let current_pattern = pattern.iter().skip(start).copied().collect::<Vec<_>>();
     if let Some(count) = cache.get(&current_pattern) {
         return *count;
     }
@@ -97,11 +121,11 @@ fn how_many_different_ways_possible_recursive(
     let mut total = 0;
 
     for a in available {
-        if does_match(a, &pattern, start) {
+        if does_match(a, pattern, start) {
             total += how_many_different_ways_possible_recursive(
                 start + a.len(),
-                &pattern,
-                &available,
+                pattern,
+                available,
                 cache,
             );
         }
@@ -128,6 +152,7 @@ fn does_match(available: &[char], pattern: &[char], pattern_start: usize) -> boo
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "r, wr, b, g, bwu, rb, gb, br
 
@@ -140,25 +165,33 @@ bwurrg
 brgr
 bbrgwb";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 6);
+        assert_eq!(solution_part1(EXAMPLE), Ok(6));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 228);
+        let expected = real_answer!(19, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(19)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE), 16);
+        assert_eq!(solution_part2(EXAMPLE), Ok(16));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 584553405070389);
+        let expected = real_answer!(19, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(19)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
@@ -1,62 +1,98 @@
 // https://adventofcode.com/2024/day/20
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
 
-use crate::{Distance, Position};
+use crate::{
+    search,
+    solution::{Args, Params, Solution},
+    Distance, Grid, ParseError, Position, Source,
+};
 
-pub fn solution_part1(input: &str, picoseconds_to_save: usize) -> usize {
-    let race = Race::from_input(input);
-    race.how_many_cheats_to_save(2, picoseconds_to_save)
+pub fn solution_part1(input: &str, picoseconds_to_save: usize) -> Result<usize, ParseError> {
+    let race = Race::from_input(input)?;
+    Ok(race.how_many_cheats_to_save(2, picoseconds_to_save))
 }
 
-pub fn solution_part2(input: &str, picoseconds_to_save: usize) -> usize {
-    let race = Race::from_input(input);
-    race.how_many_cheats_to_save(20, picoseconds_to_save)
+pub fn solution_part2(input: &str, picoseconds_to_save: usize) -> Result<usize, ParseError> {
+    let race = Race::from_input(input)?;
+    Ok(race.how_many_cheats_to_save(20, picoseconds_to_save))
 }
 
-struct Race {
-    width: usize,
-    height: usize,
-    walls: Vec<Vec<bool>>,
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Parsed<'a> = Race;
+    type Params = CheatThreshold;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Race, ParseError> {
+        Race::from_input(input)
+    }
+
+    fn part1(race: Race, threshold: &CheatThreshold) -> usize {
+        race.how_many_cheats_to_save(2, threshold.picoseconds_to_save)
+    }
+
+    fn part2(race: Race, threshold: &CheatThreshold) -> usize {
+        race.how_many_cheats_to_save(20, threshold.picoseconds_to_save)
+    }
+}
+
+pub struct CheatThreshold {
+    pub picoseconds_to_save: usize,
+}
+
+impl Default for CheatThreshold {
+    fn default() -> Self {
+        Self {
+            picoseconds_to_save: 100,
+        }
+    }
+}
+
+impl Params for CheatThreshold {
+    fn from_args(args: &mut Args) -> Result<Self, String> {
+        Ok(Self {
+            picoseconds_to_save: args
+                .next_or("picoseconds_to_save", Self::default().picoseconds_to_save)?,
+        })
+    }
+}
+
+pub struct Race {
+    walls: Grid<bool>,
     start: Position,
     end: Position,
 }
 
 impl Race {
-    fn from_input(input: &str) -> Self {
-        let lines = input
-            .lines()
-            .map(|l| l.chars().collect::<Vec<char>>())
-            .collect::<Vec<Vec<char>>>();
-        let height = lines.len();
-        let width = lines[0].len();
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
         let mut start_position = None;
         let mut end_position = None;
-        let mut walls = vec![];
-
-        for (y, line) in lines.into_iter().enumerate() {
-            let mut walls_line = vec![false; width];
-
-            for (x, c) in line.into_iter().enumerate() {
-                match c {
-                    '.' => walls_line[x] = false,
-                    '#' => walls_line[x] = true,
-                    'S' => start_position = Some(Position(x, y)),
-                    'E' => end_position = Some(Position(x, y)),
-                    _ => unreachable!(),
-                }
-            }
 
-            walls.push(walls_line);
-        }
+        let walls = Grid::from_input(&source, input, |c, position| match c {
+            '.' => Ok(false),
+            '#' => Ok(true),
+            'S' if start_position.is_none() => {
+                start_position = Some(position);
+                Ok(false)
+            }
+            'E' if end_position.is_none() => {
+                end_position = Some(position);
+                Ok(false)
+            }
+            _ => Err("`.`, `#` or a single `S` and `E`"),
+        })?;
 
-        Self {
-            width,
-            height,
+        Ok(Self {
             walls,
-            end: end_position.unwrap(),
-            start: start_position.unwrap(),
-        }
+            end: end_position.ok_or_else(|| source.end("the end `E`"))?,
+            start: start_position.ok_or_else(|| source.end("the start `S`"))?,
+        })
     }
 
     fn how_many_cheats_to_save(
@@ -80,11 +116,13 @@ impl Race {
                     }
 
                     let distance_cheating = Distance(x, y);
-                    let distance_cheating_steps = x.abs() as usize + y.abs() as usize;
+                    let distance_cheating_steps = distance_cheating.manhattan();
 
-                    let Some(reached_position) =
-                        position.move_by(&distance_cheating, self.width, self.height)
-                    else {
+                    let Some(reached_position) = position.move_by(
+                        &distance_cheating,
+                        self.walls.width(),
+                        self.walls.height(),
+                    ) else {
                         continue;
                     };
 
@@ -104,56 +142,24 @@ impl Race {
     }
 
     fn race_to_end_path(&self) -> Vec<Position> {
-        let mut paths = BTreeMap::<usize, Vec<(Position, Vec<Position>)>>::new();
-        paths.insert(0, vec![(self.start.clone(), vec![self.start.clone()])]);
-
-        let mut visited = (0..self.height)
-            .map(|_| (0..self.width).map(|_| usize::MAX).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-
-        loop {
-            let (picoseconds, positions) = paths.pop_first().expect("there is always a path");
-
-            for (position, path) in positions {
-                if visited[position.1][position.0] <= picoseconds {
-                    continue;
-                }
-
-                visited[position.1][position.0] = picoseconds;
-
-                let next_picoseconds = picoseconds + 1;
-
-                let up = position.up();
-                let down = position.down_bounded(self.height);
-                let right = position.right_bounded(self.width);
-                let left = position.left();
-
-                for next_position in [up, down, right, left].into_iter().filter_map(|p| p) {
-                    if self.walls[next_position.1][next_position.0] {
-                        continue;
-                    }
-
-                    let mut next_path = path.clone();
-                    next_path.push(next_position.clone());
-
-                    if next_position == self.end {
-                        return next_path;
-                    }
-
-                    if let Some(paths_mut) = paths.get_mut(&(next_picoseconds)) {
-                        paths_mut.push((next_position, next_path));
-                    } else {
-                        paths.insert(next_picoseconds, vec![(next_position, next_path)]);
-                    }
-                }
-            }
-        }
+        search::bfs(
+            self.start.clone(),
+            |position| {
+                self.walls
+                    .neighbours(position)
+                    .filter(|next_position| !self.walls[next_position])
+            },
+            |position| *position == self.end,
+        )
+        .path(&self.end)
+        .expect("there is always a path")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "###############
 #...#...#.....#
@@ -171,35 +177,43 @@ mod tests {
 #...#...#...###
 ###############";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE, 70), 0);
-        assert_eq!(solution_part1(EXAMPLE, 50), 1);
-        assert_eq!(solution_part1(EXAMPLE, 40), 2);
-        assert_eq!(solution_part1(EXAMPLE, 30), 4);
-        assert_eq!(solution_part1(EXAMPLE, 20), 5);
-        assert_eq!(solution_part1(EXAMPLE, 10), 10);
-        assert_eq!(solution_part1(EXAMPLE, 5), 16);
+        assert_eq!(solution_part1(EXAMPLE, 70), Ok(0));
+        assert_eq!(solution_part1(EXAMPLE, 50), Ok(1));
+        assert_eq!(solution_part1(EXAMPLE, 40), Ok(2));
+        assert_eq!(solution_part1(EXAMPLE, 30), Ok(4));
+        assert_eq!(solution_part1(EXAMPLE, 20), Ok(5));
+        assert_eq!(solution_part1(EXAMPLE, 10), Ok(10));
+        assert_eq!(solution_part1(EXAMPLE, 5), Ok(16));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT, 100), 1524);
+        let expected = real_answer!(20, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(20), 100).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE, 80), 0);
-        assert_eq!(solution_part2(EXAMPLE, 75), 3);
-        assert_eq!(solution_part2(EXAMPLE, 70), 41);
-        assert_eq!(solution_part2(EXAMPLE, 60), 129);
-        assert_eq!(solution_part2(EXAMPLE, 50), 285);
+        assert_eq!(solution_part2(EXAMPLE, 80), Ok(0));
+        assert_eq!(solution_part2(EXAMPLE, 75), Ok(3));
+        assert_eq!(solution_part2(EXAMPLE, 70), Ok(41));
+        assert_eq!(solution_part2(EXAMPLE, 60), Ok(129));
+        assert_eq!(solution_part2(EXAMPLE, 50), Ok(285));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT, 100), 1033746);
+        let expected = real_answer!(20, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(20), 100).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
@@ -0,0 +1,225 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use crate::{search, Distance, Grid, ParseError, Position, Source};
+
+/// The keys a robot arm is driven with, `A` pressing the key the arm points at.
+const ARROWS_AND_A: [char; 5] = ['^', 'v', '<', '>', 'A'];
+
+/// Keys laid out on a grid, with the key the arm of the robot pressing them starts on.
+#[derive(Debug, Clone)]
+pub struct Keypad {
+    start: char,
+    /// For each key and each key to press next, the arrows that move the arm there along a
+    /// shortest way, then `A`, for every shortest way.
+    paths: HashMap<(char, char), Vec<Vec<char>>>,
+}
+
+impl Keypad {
+    pub fn numeric() -> Self {
+        Self::from_layout("789\n456\n123\n 0A").expect("the numeric keypad to be valid")
+    }
+
+    pub fn directional() -> Self {
+        Self::from_layout(" ^A\n<v>").expect("the directional keypad to be valid")
+    }
+
+    /// A key per character, spaces being holes the arm can't go over. Lines must be the same
+    /// length and each key must appear once. The arm starts on `A`, or on the first key when
+    /// there's none.
+    pub fn from_layout(layout: &str) -> Result<Self, ParseError> {
+        let source = Source::new(layout);
+        let mut seen = HashSet::new();
+        let grid = Grid::from_input(&source, layout, |c, _| match c {
+            ' ' => Ok(None),
+            _ if seen.insert(c) => Ok(Some(c)),
+            _ => Err("a key that isn't on the keypad yet"),
+        })?;
+
+        let keys = grid
+            .iter()
+            .filter_map(|(position, key)| key.map(|key| (key, position)))
+            .collect::<Vec<_>>();
+        let start = match keys.first() {
+            _ if seen.contains(&'A') => 'A',
+            Some((key, _)) => *key,
+            None => return Err(source.end("a key")),
+        };
+
+        let mut paths = HashMap::new();
+        for (from_key, from_pos) in &keys {
+            for (to_key, to_pos) in &keys {
+                let to_press = keypad_shortest_paths(&grid, from_pos, to_pos);
+                if to_press.is_empty() {
+                    let key = layout
+                        .lines()
+                        .nth(to_pos.y())
+                        .and_then(|line| {
+                            let (i, _) = line.char_indices().nth(to_pos.x())?;
+                            Some(&line[i..])
+                        })
+                        .expect("keys to be in the layout");
+                    return Err(source.char_error(key, "a key reachable from the others"));
+                }
+
+                paths.insert((*from_key, *to_key), to_press);
+            }
+        }
+
+        Ok(Self { start, paths })
+    }
+
+    pub fn has_key(&self, key: char) -> bool {
+        self.paths.contains_key(&(key, key))
+    }
+}
+
+fn keypad_shortest_paths(
+    keypad: &Grid<Option<char>>,
+    from_pos: &Position,
+    to_pos: &Position,
+) -> Vec<Vec<char>> {
+    let result = search::bfs(
+        from_pos.clone(),
+        |position| {
+            keypad
+                .neighbours(position)
+                .filter(|p| keypad[p].is_some())
+                .collect::<Vec<_>>()
+        },
+        |position| position == to_pos,
+    );
+
+    result
+        .all_paths(to_pos)
+        .into_iter()
+        .map(|path| {
+            path.windows(2)
+                .map(|step| match Distance::between(&step[0], &step[1]) {
+                    Distance(0, -1) => '^',
+                    Distance(0, 1) => 'v',
+                    Distance(1, 0) => '>',
+                    Distance(-1, 0) => '<',
+                    _ => unreachable!("paths only move to a neighbour"),
+                })
+                .chain(['A'])
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainError {
+    Empty,
+    /// A keypad driving the arm of the previous one lacks an arrow or `A`.
+    MissingKey {
+        keypad: usize,
+        key: char,
+    },
+}
+
+impl Display for ChainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChainError::Empty => write!(f, "a chain needs at least one keypad"),
+            ChainError::MissingKey { keypad, key } => {
+                write!(f, "keypad {} has no `{}` key to drive a robot", keypad, key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChainError {}
+
+/// Keypads from the one of the door to the one you press, each robot arm but the first one being
+/// driven with the keypad that comes after its own.
+#[derive(Debug, Clone)]
+pub struct KeypadChain {
+    keypads: Vec<Keypad>,
+}
+
+impl KeypadChain {
+    /// Every keypad but the first one needs the arrows and `A`, whatever their layout.
+    pub fn new(keypads: Vec<Keypad>) -> Result<Self, ChainError> {
+        if keypads.is_empty() {
+            return Err(ChainError::Empty);
+        }
+        for (i, keypad) in keypads.iter().enumerate().skip(1) {
+            if let Some(key) = ARROWS_AND_A.into_iter().find(|key| !keypad.has_key(*key)) {
+                return Err(ChainError::MissingKey { keypad: i, key });
+            }
+        }
+
+        Ok(Self { keypads })
+    }
+
+    /// The numeric keypad then `directional_keypads` directional keypads, like the puzzle.
+    pub fn puzzle(directional_keypads: usize) -> Self {
+        let keypads = std::iter::once(Keypad::numeric())
+            .chain(std::iter::repeat_n(
+                Keypad::directional(),
+                directional_keypads,
+            ))
+            .collect();
+        Self::new(keypads).expect("the puzzle keypads to drive robots")
+    }
+
+    /// The fewest presses on the last keypad that type `code` on the first one, which must have
+    /// every key of the code.
+    pub fn presses(&self, code: &[char]) -> Result<usize, ChainError> {
+        let mut memo = HashMap::new();
+        let mut prev = self.keypads[0].start;
+
+        code.iter()
+            .map(|&key| {
+                let presses = self.presses_recursive(&mut memo, 0, prev, key);
+                prev = key;
+                presses
+            })
+            .sum()
+    }
+
+    /// The fewest presses on the last keypad that move the arm on keypad `level` from `start_key`
+    /// to `to_key` and press it, the arms of the keypads after it being back on `A`.
+    fn presses_recursive(
+        &self,
+        memo: &mut HashMap<(usize, char, char), usize>,
+        level: usize,
+        start_key: char,
+        to_key: char,
+    ) -> Result<usize, ChainError> {
+        let paths =
+            self.keypads[level]
+                .paths
+                .get(&(start_key, to_key))
+                .ok_or(ChainError::MissingKey {
+                    keypad: level,
+                    key: to_key,
+                })?;
+        if level + 1 == self.keypads.len() {
+            return Ok(1);
+        }
+        if let Some(presses) = memo.get(&(level, start_key, to_key)) {
+            return Ok(*presses);
+        }
+
+        let presses = paths.iter().try_fold(usize::MAX, |fewest, path| {
+            let mut prev_key = self.keypads[level + 1].start;
+
+            let presses = path
+                .iter()
+                .map(|&key| {
+                    let presses = self.presses_recursive(memo, level + 1, prev_key, key);
+                    prev_key = key;
+                    presses
+                })
+                .sum::<Result<usize, _>>()?;
+            Ok(fewest.min(presses))
+        })?;
+
+        memo.insert((level, start_key, to_key), presses);
+        Ok(presses)
+    }
+}
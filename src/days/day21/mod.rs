@@ -1,178 +1,64 @@
 // https://adventofcode.com/2024/day/21
 
-use std::collections::{hash_map::Entry, BTreeMap, HashMap};
+pub use keypad::{ChainError, Keypad, KeypadChain};
 
-use crate::Position;
+use crate::{solution::Solution, ParseError, Source};
 
-pub fn solution_part1(input: &str) -> usize {
-    let codes = Codes::from_input(input);
-    codes.complexity(3)
-}
+mod keypad;
 
-pub fn solution_part2(input: &str) -> usize {
-    let codes = Codes::from_input(input);
-    codes.complexity(26)
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let codes = Codes::from_input(input)?;
+    Ok(codes.puzzle_complexity(3))
 }
 
-struct Codes(Vec<(Vec<NumericKey>, usize)>);
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum NumericKey {
-    A,
-    N1,
-    N2,
-    N3,
-    N4,
-    N5,
-    N6,
-    N7,
-    N8,
-    N9,
-    N0,
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let codes = Codes::from_input(input)?;
+    Ok(codes.puzzle_complexity(26))
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum DirectionalKey {
-    A,
-    Up,
-    Left,
-    Down,
-    Right,
-}
+pub struct Day21;
 
-fn numeric_keypad_shortest_paths() -> HashMap<(NumericKey, NumericKey), Vec<Vec<DirectionalKey>>> {
-    let all_keys = [
-        (NumericKey::A, Position(2, 3)),
-        (NumericKey::N0, Position(1, 3)),
-        (NumericKey::N1, Position(0, 2)),
-        (NumericKey::N2, Position(1, 2)),
-        (NumericKey::N3, Position(2, 2)),
-        (NumericKey::N4, Position(0, 1)),
-        (NumericKey::N5, Position(1, 1)),
-        (NumericKey::N6, Position(2, 1)),
-        (NumericKey::N7, Position(0, 0)),
-        (NumericKey::N8, Position(1, 0)),
-        (NumericKey::N9, Position(2, 0)),
-    ];
-    let keypad = vec![
-        vec![true, true, true],
-        vec![true, true, true],
-        vec![true, true, true],
-        vec![false, true, true],
-    ];
-
-    let mut paths = HashMap::new();
-
-    for (from_key, from_pos) in all_keys.iter() {
-        for (to_key, to_pos) in all_keys.iter() {
-            let directional_keys_to_press = keypad_shortest_paths(&keypad, from_pos, to_pos);
-
-            paths.insert(
-                (from_key.clone(), to_key.clone()),
-                directional_keys_to_press,
-            );
-        }
-    }
+impl Solution for Day21 {
+    const DAY: u8 = 21;
 
-    paths
-}
+    type Parsed<'a> = Codes;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Codes, ParseError> {
+        Codes::from_input(input)
+    }
 
-fn directional_keypad_shortest_paths(
-) -> HashMap<(DirectionalKey, DirectionalKey), Vec<Vec<DirectionalKey>>> {
-    let all_keys = [
-        (DirectionalKey::A, Position(2, 0)),
-        (DirectionalKey::Up, Position(1, 0)),
-        (DirectionalKey::Down, Position(1, 1)),
-        (DirectionalKey::Left, Position(0, 1)),
-        (DirectionalKey::Right, Position(2, 1)),
-    ];
-    let keypad = vec![vec![false, true, true], vec![true, true, true]];
-
-    let mut paths = HashMap::new();
-
-    for (from_key, from_pos) in all_keys.iter() {
-        for (to_key, to_pos) in all_keys.iter() {
-            let directional_keys_to_press = keypad_shortest_paths(&keypad, from_pos, to_pos);
-
-            paths.insert(
-                (from_key.clone(), to_key.clone()),
-                directional_keys_to_press,
-            );
-        }
+    fn part1(codes: Codes, _: &()) -> usize {
+        codes.puzzle_complexity(3)
     }
 
-    paths
+    fn part2(codes: Codes, _: &()) -> usize {
+        codes.puzzle_complexity(26)
+    }
 }
 
-fn keypad_shortest_paths(
-    keypad: &Vec<Vec<bool>>,
-    from_pos: &Position,
-    to_pos: &Position,
-) -> Vec<Vec<DirectionalKey>> {
-    if from_pos == to_pos {
-        return vec![vec![DirectionalKey::A]];
+pub struct Codes(Vec<(Vec<char>, usize)>);
+
+impl Codes {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        Self::parse(input, |c| c.is_ascii_digit() || c == 'A', "a digit or `A`")
     }
 
-    let height = keypad.len();
-    let width = keypad[0].len();
-
-    let mut path_finding_paths = BTreeMap::<usize, Vec<(Position, Vec<DirectionalKey>)>>::new();
-    path_finding_paths.insert(0, vec![(from_pos.clone(), vec![])]);
-    let mut visited = HashMap::<Position, usize>::new();
-
-    let mut shortest_paths = vec![];
-
-    while let Some((steps, positions)) = path_finding_paths.pop_first() {
-        for (position, directional_keys_to_press) in positions {
-            let up = position.up().map(|p| (p, DirectionalKey::Up));
-            let down = position
-                .down_bounded(height)
-                .map(|p| (p, DirectionalKey::Down));
-            let right = position
-                .right_bounded(width)
-                .map(|p| (p, DirectionalKey::Right));
-            let left = position.left().map(|p| (p, DirectionalKey::Left));
-
-            let next_steps = steps + 1;
-
-            for (next_pos, directional_key) in [up, down, right, left].into_iter().filter_map(|p| p)
-            {
-                match visited.entry(next_pos.clone()) {
-                    Entry::Occupied(mut occupied_entry) => {
-                        if *occupied_entry.get() < next_steps {
-                            continue;
-                        } else {
-                            *occupied_entry.get_mut() = next_steps;
-                        }
-                    }
-                    Entry::Vacant(vacant_entry) => {
-                        vacant_entry.insert(next_steps);
-                    }
-                }
-                let mut directional_keys_to_press = directional_keys_to_press.clone();
-                directional_keys_to_press.push(directional_key);
-
-                if keypad[next_pos.1][next_pos.0] {
-                    if &next_pos == to_pos {
-                        directional_keys_to_press.push(DirectionalKey::A);
-                        shortest_paths.push(directional_keys_to_press);
-                    } else if let Some(paths_mut) = path_finding_paths.get_mut(&next_steps) {
-                        paths_mut.push((next_pos, directional_keys_to_press));
-                    } else {
-                        path_finding_paths
-                            .insert(next_steps, vec![(next_pos, directional_keys_to_press)]);
-                    }
-                }
-            }
-        }
+    /// Codes typed on another keypad than the numeric one, their value being their digits.
+    pub fn from_input_for(input: &str, keypad: &Keypad) -> Result<Self, ParseError> {
+        Self::parse(input, |c| keypad.has_key(c), "a key of the keypad")
     }
 
-    shortest_paths
-}
+    fn parse(
+        input: &str,
+        is_key: impl Fn(char) -> bool,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let source = Source::new(input);
 
-impl Codes {
-    fn from_input(input: &str) -> Self {
-        Self(
+        Ok(Self(
             input
                 .lines()
                 .map(|line| {
@@ -180,138 +66,41 @@ impl Codes {
                         .chars()
                         .filter_map(|c| c.to_digit(10))
                         .fold(0usize, |acc, n| acc * 10 + n as usize);
-                    (
-                        line.chars()
-                            .map(|c| match c {
-                                '0' => NumericKey::N0,
-                                '1' => NumericKey::N1,
-                                '2' => NumericKey::N2,
-                                '3' => NumericKey::N3,
-                                '4' => NumericKey::N4,
-                                '5' => NumericKey::N5,
-                                '6' => NumericKey::N6,
-                                '7' => NumericKey::N7,
-                                '8' => NumericKey::N8,
-                                '9' => NumericKey::N9,
-                                'A' => NumericKey::A,
-                                _ => panic!("Invalid input"),
-                            })
-                            .collect::<Vec<NumericKey>>(),
-                        value,
-                    )
-                })
-                .collect::<Vec<(Vec<NumericKey>, usize)>>(),
-        )
-    }
-
-    fn complexity(&self, directional_keypads: usize) -> usize {
-        let numeric_keys_paths = numeric_keypad_shortest_paths();
-        let directional_keys_paths = directional_keypad_shortest_paths();
-
-        let mut complexity = 0;
-
-        for (keys, keys_value) in self.0.iter() {
-            let mut directions_possibilities: Vec<Vec<DirectionalKey>> = vec![vec![]];
-
-            let mut prev = NumericKey::A;
-            for i in 0..keys.len() {
-                let current = keys[i];
-
-                let paths = &numeric_keys_paths[&(prev, current)];
-                let prev_directions_possibilities = directions_possibilities;
-                directions_possibilities = vec![];
-
-                for possibility in prev_directions_possibilities {
-                    for path in paths.iter() {
-                        let mut possibility = possibility.clone();
-                        possibility.extend(path.clone());
-                        directions_possibilities.push(possibility);
-                    }
-                }
-                prev = current;
-            }
-
-            let mut memo: HashMap<(DirectionalKey, DirectionalKey, usize), usize> = HashMap::new();
-
-            let keys_to_press = directions_possibilities
-                .into_iter()
-                .map(|directions| {
-                    let mut prev_direction = DirectionalKey::A;
-
-                    directions
-                        .into_iter()
-                        .map(|direction| {
-                            let complexity = complexity_recursive(
-                                &mut memo,
-                                &directional_keys_paths,
-                                directional_keypads - 1,
-                                prev_direction,
-                                direction,
-                            );
-                            prev_direction = direction;
-                            complexity
+                    let keys = line
+                        .char_indices()
+                        .map(|(i, c)| match c {
+                            _ if is_key(c) => Ok(c),
+                            _ => Err(source.char_error(&line[i..], expected)),
                         })
-                        .sum::<usize>()
-                })
-                .min()
-                .unwrap();
+                        .collect::<Result<Vec<char>, _>>()?;
 
-            complexity += keys_to_press * keys_value;
-        }
-
-        complexity
+                    Ok((keys, value))
+                })
+                .collect::<Result<Vec<(Vec<char>, usize)>, _>>()?,
+        ))
     }
-}
 
-fn complexity_recursive(
-    memo: &mut HashMap<(DirectionalKey, DirectionalKey, usize), usize>,
-    directional_keys_paths: &HashMap<(DirectionalKey, DirectionalKey), Vec<Vec<DirectionalKey>>>,
-    directional_keypads: usize,
-    start_key: DirectionalKey,
-    to_key: DirectionalKey,
-) -> usize {
-    if directional_keypads == 0 {
-        return 1;
+    /// The sum for each code of the fewest presses on the last keypad of the chain that type it
+    /// on the first one, times its value.
+    pub fn complexity(&self, chain: &KeypadChain) -> Result<usize, ChainError> {
+        self.0
+            .iter()
+            .map(|(keys, keys_value)| Ok(chain.presses(keys)? * keys_value))
+            .sum()
     }
 
-    let paths = &directional_keys_paths[&(start_key, to_key)];
-    paths
-        .iter()
-        .map(|path| {
-            let mut prev_key = DirectionalKey::A;
-
-            path.iter()
-                .map(|key| {
-                    let new_score = if let Some(memo_score) =
-                        memo.get(&(prev_key, *key, directional_keypads))
-                    {
-                        *memo_score
-                    } else {
-                        let new_score = complexity_recursive(
-                            memo,
-                            directional_keys_paths,
-                            directional_keypads - 1,
-                            prev_key,
-                            *key,
-                        );
-                        *memo
-                            .entry((prev_key, *key, directional_keypads))
-                            .or_default() = new_score;
-                        new_score
-                    };
-                    prev_key = *key;
-
-                    new_score
-                })
-                .sum::<usize>()
-        })
-        .min()
-        .unwrap()
+    /// The complexity on the puzzle's chain, which has the keys of the codes parsed from the
+    /// puzzle input.
+    fn puzzle_complexity(&self, directional_keypads: usize) -> usize {
+        self.complexity(&KeypadChain::puzzle(directional_keypads))
+            .expect("the numeric keypad to have every digit and `A`")
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "029A
 980A
@@ -319,40 +108,150 @@ mod tests {
 456A
 379A";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_sub_example_1() {
-        assert_eq!(Codes::from_input("029A").complexity(1), 12 * 29);
+        assert_eq!(
+            Codes::from_input("029A")
+                .unwrap()
+                .complexity(&KeypadChain::puzzle(1)),
+            Ok(12 * 29)
+        );
     }
 
     #[test]
     fn test_part1_sub_example_2() {
-        assert_eq!(Codes::from_input("029A").complexity(2), 28 * 29);
+        assert_eq!(
+            Codes::from_input("029A")
+                .unwrap()
+                .complexity(&KeypadChain::puzzle(2)),
+            Ok(28 * 29)
+        );
     }
 
     #[test]
     fn test_part1_sub_example_3() {
-        assert_eq!(Codes::from_input("029A").complexity(3), 68 * 29);
+        assert_eq!(
+            Codes::from_input("029A")
+                .unwrap()
+                .complexity(&KeypadChain::puzzle(3)),
+            Ok(68 * 29)
+        );
     }
 
     #[test]
     fn test_part1_sub_example_4() {
-        assert_eq!(Codes::from_input("3").complexity(3), 12 * 3);
+        assert_eq!(
+            Codes::from_input("3")
+                .unwrap()
+                .complexity(&KeypadChain::puzzle(3)),
+            Ok(12 * 3)
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            solution_part1("029A\n98B"),
+            Err(ParseError {
+                line: 2,
+                column: 3,
+                expected: "a digit or `A`".to_string(),
+                found: Some("B".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_keypad_layouts() {
+        let codes = Codes::from_input(EXAMPLE).unwrap();
+        let directional = Keypad::from_layout(" ^A\n<v>").unwrap();
+        let chain = KeypadChain::new(vec![
+            Keypad::from_layout("789\n456\n123\n 0A").unwrap(),
+            directional.clone(),
+            directional.clone(),
+            directional,
+        ])
+        .unwrap();
+        assert_eq!(codes.complexity(&chain), Ok(126384));
+
+        // the phone arm starts on `1`, the arrows of the middle keypad are in a row
+        let phone = Keypad::from_layout("123\n456\n789\n*0#").unwrap();
+        let codes = Codes::from_input_for("2", &phone).unwrap();
+        let chain = KeypadChain::new(vec![
+            phone,
+            Keypad::from_layout("A<>^v").unwrap(),
+            Keypad::directional(),
+        ])
+        .unwrap();
+        assert_eq!(codes.complexity(&chain), Ok(6 * 2));
+
+        assert_eq!(
+            Keypad::from_layout("12\n 1").unwrap_err().expected,
+            "a key that isn't on the keypad yet"
+        );
+        assert_eq!(Keypad::from_layout("1 2").unwrap_err().column, 3);
+        assert_eq!(
+            KeypadChain::new(vec![
+                Keypad::numeric(),
+                Keypad::from_layout("<^>A").unwrap()
+            ])
+            .unwrap_err(),
+            ChainError::MissingKey {
+                keypad: 1,
+                key: 'v'
+            }
+        );
+        assert_eq!(
+            Codes::from_input_for("12A", &Keypad::from_layout("123").unwrap())
+                .err()
+                .map(|error| error.column),
+            Some(3)
+        );
+
+        // keys that the first keypad of the chain lacks
+        let phone_codes =
+            Codes::from_input_for("*0#", &Keypad::from_layout("123\n456\n789\n*0#").unwrap())
+                .unwrap();
+        assert_eq!(
+            phone_codes.complexity(&KeypadChain::puzzle(2)),
+            Err(ChainError::MissingKey {
+                keypad: 0,
+                key: '*'
+            })
+        );
+        assert_eq!(
+            KeypadChain::new(vec![Keypad::directional()])
+                .unwrap()
+                .presses(&['0']),
+            Err(ChainError::MissingKey {
+                keypad: 0,
+                key: '0'
+            })
+        );
     }
 
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 126384);
+        assert_eq!(solution_part1(EXAMPLE), Ok(126384));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 278568);
+        let expected = real_answer!(21, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(21)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 341460772681012);
+        let expected = real_answer!(21, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(21)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
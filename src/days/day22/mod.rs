@@ -1,27 +1,54 @@
 // https://adventofcode.com/2024/day/22
 
-pub fn solution_part1(input: &str) -> usize {
-    let market = MonkeyMarket::from_input(input);
-    market.sum_nth_secret_numbers(2000)
+use crate::{solution::Solution, ParseError, Source};
+
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let market = MonkeyMarket::from_input(input)?;
+    Ok(market.sum_nth_secret_numbers(2000))
+}
+
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let market = MonkeyMarket::from_input(input)?;
+    Ok(market.most_bananas_after_same_sequence_for_nth_secret_numbers(2000))
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    let market = MonkeyMarket::from_input(input);
-    market.most_bananas_after_same_sequence_for_nth_secret_numbers(2000)
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+
+    type Parsed<'a> = MonkeyMarket;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<MonkeyMarket, ParseError> {
+        MonkeyMarket::from_input(input)
+    }
+
+    fn part1(market: MonkeyMarket, _: &()) -> usize {
+        market.sum_nth_secret_numbers(2000)
+    }
+
+    fn part2(market: MonkeyMarket, _: &()) -> usize {
+        market.most_bananas_after_same_sequence_for_nth_secret_numbers(2000)
+    }
 }
 
-struct MonkeyMarket {
+pub struct MonkeyMarket {
     initial_secret_numbers: Vec<usize>,
 }
 
 impl MonkeyMarket {
-    fn from_input(input: &str) -> Self {
-        Self {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+
+        Ok(Self {
             initial_secret_numbers: input
                 .lines()
-                .map(|l| l.parse::<usize>().unwrap())
-                .collect::<Vec<_>>(),
-        }
+                .map(|l| source.number::<usize>(l))
+                .collect::<Result<Vec<_>, _>>()?,
+        })
     }
 
     fn sum_nth_secret_numbers(self, nth: usize) -> usize {
@@ -68,13 +95,13 @@ impl MonkeyMarket {
                 let difference4 = (next_secret_number % 10) as isize
                     - (history[(history_i + 3) % 4] % 10) as isize;
 
-                let encoding1 = difference1.abs() as usize * 10usize.pow(2)
+                let encoding1 = difference1.unsigned_abs() * 10usize.pow(2)
                     + if difference1.is_negative() { 1 } else { 0 };
-                let encoding2 = difference2.abs() as usize * 10usize.pow(3)
+                let encoding2 = difference2.unsigned_abs() * 10usize.pow(3)
                     + if difference2.is_negative() { 2 } else { 0 };
-                let encoding3 = difference3.abs() as usize * 10usize.pow(4)
+                let encoding3 = difference3.unsigned_abs() * 10usize.pow(4)
                     + if difference3.is_negative() { 4 } else { 0 };
-                let encoding4 = difference4.abs() as usize * 10usize.pow(5)
+                let encoding4 = difference4.unsigned_abs() * 10usize.pow(5)
                     + if difference4.is_negative() { 8 } else { 0 };
 
                 let encoding: usize = encoding1 + encoding2 + encoding3 + encoding4;
@@ -103,6 +130,7 @@ fn next_secret_number(mut sn: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "1
 10
@@ -114,8 +142,6 @@ mod tests {
 3
 2024";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_pre_example() {
         let result = MonkeyMarket {
@@ -127,21 +153,31 @@ mod tests {
 
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 37327623);
+        assert_eq!(solution_part1(EXAMPLE), Ok(37327623));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 13185239446);
+        let expected = real_answer!(22, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(22)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE2), 23);
+        assert_eq!(solution_part2(EXAMPLE2), Ok(23));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 1501);
+        let expected = real_answer!(22, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(22)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
@@ -2,33 +2,63 @@
 
 use std::collections::{BTreeSet, HashMap, HashSet};
 
-pub fn solution_part1(input: &str) -> usize {
-    let lan_party = LanParty::from_input(input);
-    lan_party.count_groups_of_3_with_computer_starting_with_t()
+use crate::{solution::Solution, ParseError, Source};
+
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let lan_party = LanParty::from_input(input)?;
+    Ok(lan_party.count_groups_of_3_with_computer_starting_with_t())
 }
 
-pub fn solution_part2(input: &str) -> String {
-    let lan_party = LanParty::from_input(input);
-    lan_party.password_of_biggest_lan_connection()
+pub fn solution_part2(input: &str) -> Result<String, ParseError> {
+    let lan_party = LanParty::from_input(input)?;
+    Ok(lan_party.password_of_biggest_lan_connection())
 }
 
-struct LanParty<'a> {
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Parsed<'a> = LanParty<'a>;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<LanParty<'_>, ParseError> {
+        LanParty::from_input(input)
+    }
+
+    fn part1(lan_party: LanParty<'_>, _: &()) -> usize {
+        lan_party.count_groups_of_3_with_computer_starting_with_t()
+    }
+
+    fn part2(lan_party: LanParty<'_>, _: &()) -> String {
+        lan_party.password_of_biggest_lan_connection()
+    }
+}
+
+pub struct LanParty<'a> {
     partial_graph: HashMap<&'a str, Vec<&'a str>>,
 }
 
 impl<'a> LanParty<'a> {
-    fn from_input(input: &'a str) -> Self {
-        let mut connections = input.lines().collect::<Vec<_>>();
+    fn from_input(input: &'a str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+        let mut connections = input
+            .lines()
+            .map(|connection| source.split_once(connection, "-"))
+            .collect::<Result<Vec<_>, _>>()?;
         connections.sort();
 
         let mut graph = HashMap::new();
 
-        let (left, right) = connections[0].split_once('-').unwrap();
+        let Some(&(left, right)) = connections.first() else {
+            return Err(source.end("a connection"));
+        };
         let mut last_computer = left;
         let mut edges = vec![right];
 
-        for connection in connections.into_iter().skip(1) {
-            let (left, right) = connection.split_once('-').unwrap();
+        for (left, right) in connections.into_iter().skip(1) {
             if left != last_computer {
                 graph.insert(last_computer, edges);
                 last_computer = left;
@@ -42,9 +72,9 @@ impl<'a> LanParty<'a> {
             graph.insert(last_computer, edges);
         }
 
-        Self {
+        Ok(Self {
             partial_graph: graph,
-        }
+        })
     }
 
     fn count_groups_of_3_with_computer_starting_with_t(&self) -> usize {
@@ -138,12 +168,12 @@ impl<'a> LanParty<'a> {
         for (computer, connections) in self.partial_graph.iter() {
             graph
                 .entry(*computer)
-                .or_insert_with(|| BTreeSet::new())
+                .or_insert_with(BTreeSet::new)
                 .extend(connections);
             for connected_computer in connections {
                 graph
-                    .entry(&connected_computer)
-                    .or_insert_with(|| BTreeSet::new())
+                    .entry(connected_computer)
+                    .or_insert_with(BTreeSet::new)
                     .insert(*computer);
             }
         }
@@ -155,6 +185,7 @@ impl<'a> LanParty<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "kh-tc
 qp-kh
@@ -189,28 +220,33 @@ wh-qp
 tb-vc
 td-yn";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 7);
+        assert_eq!(solution_part1(EXAMPLE), Ok(7));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 998);
+        let expected = real_answer!(23, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(23)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE), "co,de,ka,ta");
+        assert_eq!(solution_part2(EXAMPLE).as_deref(), Ok("co,de,ka,ta"));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
+        let expected = real_answer!(23, Part::Two);
         assert_eq!(
-            solution_part2(INPUT),
-            "cc,ff,fh,fr,ny,oa,pl,rg,uj,wd,xn,xs,zw"
+            solution_part2(&real_input!(23)).map(|answer| answer.to_string()),
+            Ok(expected)
         );
     }
 }
@@ -0,0 +1,312 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::{Connection, Gate, MonitoringDevice};
+
+/// A gate that doesn't fit the ripple-carry adder pattern of its bit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdderFault {
+    pub bit: usize,
+    /// The gate as it was found, in the puzzle syntax.
+    pub gate: String,
+    /// The wire the gate should drive instead.
+    pub expected: String,
+}
+
+impl Display for AdderFault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "bit {}: `{}` should drive {}",
+            self.bit, self.gate, self.expected
+        )
+    }
+}
+
+/// The faults of an adder, along with the swaps of gate outputs that fix them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdderReport {
+    pub faults: Vec<AdderFault>,
+    pub swaps: Vec<(String, String)>,
+}
+
+impl AdderReport {
+    pub fn is_adder(&self) -> bool {
+        self.swaps.is_empty()
+    }
+
+    /// Every swapped wire, sorted.
+    pub fn swapped_wires(&self) -> Vec<&str> {
+        let mut wires = self
+            .swaps
+            .iter()
+            .flat_map(|(a, b)| [a.as_str(), b.as_str()])
+            .collect::<Vec<_>>();
+        wires.sort_unstable();
+        wires
+    }
+}
+
+/// The circuit is too far from an adder to tell which outputs were swapped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdderError {
+    /// A gate that couldn't be found, in the puzzle syntax without its output.
+    MissingGate { bit: usize, gate: String },
+    /// A wire whose gate should have been swapped is an input of the circuit.
+    NoDriver { bit: usize, wire: String },
+}
+
+impl Display for AdderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdderError::MissingGate { bit, gate } => write!(f, "bit {}: no gate `{}`", bit, gate),
+            AdderError::NoDriver { bit, wire } => {
+                write!(f, "bit {}: wire {} has no driver", bit, wire)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AdderError {}
+
+/// The connections, indexed by what they read and drive, with outputs swapped along the way.
+struct Netlist<'a> {
+    connections: Vec<Connection<'a>>,
+    drivers: HashMap<&'a str, usize>,
+    by_inputs: HashMap<(Gate, &'a str, &'a str), usize>,
+    by_input: HashMap<(Gate, &'a str), Vec<usize>>,
+    report: AdderReport,
+}
+
+impl<'a> Netlist<'a> {
+    fn new(connections: &[Connection<'a>]) -> Self {
+        let mut netlist = Self {
+            connections: connections.to_vec(),
+            drivers: HashMap::new(),
+            by_inputs: HashMap::new(),
+            by_input: HashMap::new(),
+            report: AdderReport::default(),
+        };
+
+        for (i, connection) in connections.iter().enumerate() {
+            let (left, right) = sorted(connection.left, connection.right);
+            netlist.drivers.insert(connection.result, i);
+            netlist.by_inputs.insert((connection.gate, left, right), i);
+            for input in [left, right] {
+                netlist
+                    .by_input
+                    .entry((connection.gate, input))
+                    .or_default()
+                    .push(i);
+            }
+        }
+
+        netlist
+    }
+
+    /// The wire of that name if a gate drives it.
+    fn driven(&self, name: &str) -> Option<&'a str> {
+        self.drivers.get_key_value(name).map(|(wire, _)| *wire)
+    }
+
+    fn output(&self, i: usize) -> &'a str {
+        self.connections[i].result
+    }
+
+    fn find(
+        &self,
+        gate: Gate,
+        left: &'a str,
+        right: &'a str,
+        bit: usize,
+    ) -> Result<usize, AdderError> {
+        let (left, right) = sorted(left, right);
+        self.by_inputs
+            .get(&(gate, left, right))
+            .copied()
+            .ok_or_else(|| AdderError::MissingGate {
+                bit,
+                gate: format!("{} {} {}", left, gate, right),
+            })
+    }
+
+    /// The gate of that kind reading `left` and `right`. When there's none, one of them is
+    /// assumed to be a swapped output, the right one being the other input of a gate that reads
+    /// the one that's left: the gates are fixed and the index of the gate is returned along with
+    /// the right inputs.
+    fn find_fixing(
+        &mut self,
+        gate: Gate,
+        left: &'a str,
+        right: &'a str,
+        bit: usize,
+    ) -> Result<(usize, &'a str, &'a str), AdderError> {
+        let error = match self.find(gate, left, right, bit) {
+            Ok(i) => return Ok((i, left, right)),
+            Err(error) => error,
+        };
+
+        for (kept, swapped) in [(right, left), (left, right)] {
+            let Some(&i) = self
+                .by_input
+                .get(&(gate, kept))
+                .and_then(|gates| gates.first())
+            else {
+                continue;
+            };
+            let connection = &self.connections[i];
+            let actual = if connection.left == kept {
+                connection.right
+            } else {
+                connection.left
+            };
+
+            self.swap(swapped, actual, bit)?;
+            return Ok(if kept == right {
+                (i, actual, right)
+            } else {
+                (i, left, actual)
+            });
+        }
+
+        Err(error)
+    }
+
+    /// Makes the gate `i` drive `expected`, swapping with the gate that drives it.
+    fn expect_output(&mut self, i: usize, expected: &'a str, bit: usize) -> Result<(), AdderError> {
+        let found = self.output(i);
+        if found != expected {
+            self.swap(found, expected, bit)?;
+        }
+        Ok(())
+    }
+
+    fn driver(&self, wire: &str, bit: usize) -> Result<usize, AdderError> {
+        self.drivers
+            .get(wire)
+            .copied()
+            .ok_or_else(|| AdderError::NoDriver {
+                bit,
+                wire: wire.to_string(),
+            })
+    }
+
+    fn swap(&mut self, a: &'a str, b: &'a str, bit: usize) -> Result<(), AdderError> {
+        let (i, j) = (self.driver(a, bit)?, self.driver(b, bit)?);
+        for (gate, expected) in [(i, b), (j, a)] {
+            self.report.faults.push(AdderFault {
+                bit,
+                gate: self.connections[gate].to_string(),
+                expected: expected.to_string(),
+            });
+        }
+
+        self.connections[i].result = b;
+        self.connections[j].result = a;
+        self.drivers.insert(a, j);
+        self.drivers.insert(b, i);
+        self.report.swaps.push((a.to_string(), b.to_string()));
+        Ok(())
+    }
+}
+
+fn sorted<'a>(left: &'a str, right: &'a str) -> (&'a str, &'a str) {
+    if left <= right {
+        (left, right)
+    } else {
+        (right, left)
+    }
+}
+
+impl<'a> MonitoringDevice<'a> {
+    /// Checks the gates against a ripple-carry adder of the `x` and `y` wires into the `z` wires,
+    /// bit by bit: `z = x XOR y XOR carry` and `carry' = (x AND y) OR ((x XOR y) AND carry)`,
+    /// the first bit having no carry in and the last `z` being the last carry out. Gates are
+    /// found by their inputs, so a gate driving the wrong wire shows as a gate reading an
+    /// unexpected one, or as an unexpected output.
+    pub fn check_adder(&self) -> Result<AdderReport, AdderError> {
+        let bits = self
+            .initial_values
+            .keys()
+            .filter(|wire| wire.starts_with('x'))
+            .count();
+        let names = |prefix, bit| format!("{}{:02}", prefix, bit);
+        let mut netlist = Netlist::new(&self.connections);
+
+        let wire = |netlist: &Netlist<'a>, name: String| {
+            netlist.driven(&name).or_else(|| {
+                self.initial_values
+                    .get_key_value(name.as_str())
+                    .map(|(wire, _)| *wire)
+            })
+        };
+
+        let mut carry = None;
+        for bit in 0..bits {
+            let missing = |gate: String| AdderError::MissingGate { bit, gate };
+            let x = wire(&netlist, names("x", bit)).ok_or_else(|| missing(names("x", bit)))?;
+            let y = wire(&netlist, names("y", bit)).ok_or_else(|| missing(names("y", bit)))?;
+            let z = wire(&netlist, names("z", bit)).ok_or_else(|| missing(names("z", bit)))?;
+
+            let half_sum = netlist.find(Gate::Xor, x, y, bit)?;
+            let half_carry = netlist.find(Gate::And, x, y, bit)?;
+
+            let Some(carry_in) = carry else {
+                netlist.expect_output(half_sum, z, bit)?;
+                carry = Some(netlist.output(half_carry));
+                continue;
+            };
+
+            let (sum, half_sum_wire, carry_in) =
+                netlist.find_fixing(Gate::Xor, netlist.output(half_sum), carry_in, bit)?;
+            netlist.expect_output(sum, z, bit)?;
+
+            let propagate = netlist.find(Gate::And, half_sum_wire, carry_in, bit)?;
+            let (carry_out, _, _) = netlist.find_fixing(
+                Gate::Or,
+                netlist.output(half_carry),
+                netlist.output(propagate),
+                bit,
+            )?;
+            carry = Some(netlist.output(carry_out));
+        }
+
+        if let Some(carry) = carry {
+            let last = wire(&netlist, names("z", bits)).ok_or_else(|| AdderError::MissingGate {
+                bit: bits,
+                gate: names("z", bits),
+            })?;
+            let i = netlist.driver(carry, bits)?;
+            netlist.expect_output(i, last, bits)?;
+        }
+
+        Ok(netlist.report)
+    }
+
+    /// Whether the gates make an adder once the swaps of `report` are made, which confirms the
+    /// swaps [`check_adder`](Self::check_adder) guessed along the way.
+    pub fn repairs_adder(&self, report: &AdderReport) -> bool {
+        let mut connections = self.connections.clone();
+        for (a, b) in &report.swaps {
+            let driver = |wire: &str| {
+                connections
+                    .iter()
+                    .position(|connection| connection.result == wire)
+            };
+            let (Some(i), Some(j)) = (driver(a), driver(b)) else {
+                return false;
+            };
+
+            let result = connections[i].result;
+            connections[i].result = connections[j].result;
+            connections[j].result = result;
+        }
+
+        let repaired = MonitoringDevice {
+            initial_values: self.initial_values.clone(),
+            connections,
+        };
+        repaired
+            .check_adder()
+            .is_ok_and(|report| report.is_adder())
+    }
+}
@@ -0,0 +1,281 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::{
+    BigUint, Circuit, CircuitError, Connection, Gate, MonitoringDevice, SimulatedOperation,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EquivalenceError {
+    Circuit(CircuitError),
+    /// Custom operations can only be sampled, not turned into diagrams.
+    Opaque,
+}
+
+impl Display for EquivalenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EquivalenceError::Circuit(error) => write!(f, "{}", error),
+            EquivalenceError::Opaque => write!(f, "custom operations can't be checked formally"),
+        }
+    }
+}
+
+impl std::error::Error for EquivalenceError {}
+
+impl From<CircuitError> for EquivalenceError {
+    fn from(error: CircuitError) -> Self {
+        EquivalenceError::Circuit(error)
+    }
+}
+
+/// A node of a [`Bdd`], the first two being the constants.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+struct NodeId(usize);
+
+const FALSE: NodeId = NodeId(0);
+const TRUE: NodeId = NodeId(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Node {
+    variable: usize,
+    /// Where to go when the variable is 0.
+    low: NodeId,
+    high: NodeId,
+}
+
+/// Reduced ordered binary decision diagrams sharing their nodes, so that two functions are equal
+/// exactly when they're the same node. Variables are tested in the order of their numbers.
+struct Bdd {
+    nodes: Vec<Node>,
+    unique: HashMap<Node, NodeId>,
+    computed: HashMap<(Gate, NodeId, NodeId), NodeId>,
+}
+
+impl Bdd {
+    fn new() -> Self {
+        let constant = |value| Node {
+            variable: usize::MAX,
+            low: NodeId(value),
+            high: NodeId(value),
+        };
+
+        Self {
+            nodes: vec![constant(0), constant(1)],
+            unique: HashMap::new(),
+            computed: HashMap::new(),
+        }
+    }
+
+    fn node(&mut self, variable: usize, low: NodeId, high: NodeId) -> NodeId {
+        if low == high {
+            return low;
+        }
+
+        let node = Node {
+            variable,
+            low,
+            high,
+        };
+        *self.unique.entry(node).or_insert_with(|| {
+            self.nodes.push(node);
+            NodeId(self.nodes.len() - 1)
+        })
+    }
+
+    fn variable(&mut self, variable: usize) -> NodeId {
+        self.node(variable, FALSE, TRUE)
+    }
+
+    /// The gate applied to two functions, recursing on the first variable either tests.
+    fn apply(&mut self, gate: Gate, left: NodeId, right: NodeId) -> NodeId {
+        if left.0 <= 1 && right.0 <= 1 {
+            return NodeId(gate.apply(left.0 as u64, right.0 as u64) as usize & 1);
+        }
+        if let Some(result) = self.computed.get(&(gate, left, right)) {
+            return *result;
+        }
+
+        let variable = self.nodes[left.0]
+            .variable
+            .min(self.nodes[right.0].variable);
+        let cofactors = |bdd: &Self, node: NodeId| {
+            let Node {
+                variable: tested,
+                low,
+                high,
+            } = bdd.nodes[node.0];
+            if tested == variable {
+                (low, high)
+            } else {
+                (node, node)
+            }
+        };
+        let (left_low, left_high) = cofactors(self, left);
+        let (right_low, right_high) = cofactors(self, right);
+
+        let low = self.apply(gate, left_low, right_low);
+        let high = self.apply(gate, left_high, right_high);
+        let result = self.node(variable, low, high);
+        self.computed.insert((gate, left, right), result);
+        result
+    }
+
+    fn not(&mut self, node: NodeId) -> NodeId {
+        self.apply(Gate::Not, node, node)
+    }
+
+    /// Values of the variables that make the function 1, the other variables being 0.
+    fn satisfy(&self, mut node: NodeId) -> Option<Vec<usize>> {
+        if node == FALSE {
+            return None;
+        }
+
+        let mut ones = vec![];
+        while node != TRUE {
+            let Node {
+                variable,
+                low,
+                high,
+            } = self.nodes[node.0];
+            if low == FALSE {
+                ones.push(variable);
+                node = high;
+            } else {
+                node = low;
+            }
+        }
+
+        Some(ones)
+    }
+
+    /// `x + y + carry`, cut to `width` bits, numbers being their bits, least significant first.
+    fn add(&mut self, x: &[NodeId], y: &[NodeId], mut carry: NodeId, width: usize) -> Vec<NodeId> {
+        (0..width)
+            .map(|i| {
+                let (x, y) = (bit(x, i), bit(y, i));
+                let half_sum = self.apply(Gate::Xor, x, y);
+                let sum = self.apply(Gate::Xor, half_sum, carry);
+
+                let generate = self.apply(Gate::And, x, y);
+                let propagate = self.apply(Gate::And, half_sum, carry);
+                carry = self.apply(Gate::Or, generate, propagate);
+                sum
+            })
+            .collect()
+    }
+
+    /// What the operation gives for every value of `x` and `y`, cut to `width` bits.
+    fn operation(
+        &mut self,
+        operation: &SimulatedOperation,
+        x: &[NodeId],
+        y: &[NodeId],
+        width: usize,
+    ) -> Result<Vec<NodeId>, EquivalenceError> {
+        Ok(match operation {
+            SimulatedOperation::Sum => self.add(x, y, FALSE, width),
+            SimulatedOperation::And => (0..width)
+                .map(|i| self.apply(Gate::And, bit(x, i), bit(y, i)))
+                .collect(),
+            SimulatedOperation::Difference => {
+                let complement = (0..width).map(|i| self.not(bit(y, i))).collect::<Vec<_>>();
+                self.add(x, &complement, TRUE, width)
+            }
+            SimulatedOperation::Product => {
+                let mut product = vec![FALSE; width];
+                for (shift, x) in x.iter().enumerate().take(width) {
+                    let partial = (0..width)
+                        .map(|i| match i.checked_sub(shift) {
+                            Some(j) => self.apply(Gate::And, *x, bit(y, j)),
+                            None => FALSE,
+                        })
+                        .collect::<Vec<_>>();
+                    product = self.add(&product, &partial, FALSE, width);
+                }
+                product
+            }
+            SimulatedOperation::LessThan => {
+                // from the least significant bit, the most significant one that differs decides
+                let mut less = FALSE;
+                for i in 0..x.len().max(y.len()) {
+                    let (x, y) = (bit(x, i), bit(y, i));
+                    let not_x = self.not(x);
+                    let smaller = self.apply(Gate::And, not_x, y);
+                    let equal = self.apply(Gate::Xnor, x, y);
+                    let kept = self.apply(Gate::And, equal, less);
+                    less = self.apply(Gate::Or, smaller, kept);
+                }
+                (0..width)
+                    .map(|i| if i == 0 { less } else { FALSE })
+                    .collect()
+            }
+            SimulatedOperation::Custom(_) => return Err(EquivalenceError::Opaque),
+        })
+    }
+}
+
+fn bit(number: &[NodeId], i: usize) -> NodeId {
+    number.get(i).copied().unwrap_or(FALSE)
+}
+
+impl MonitoringDevice<'_> {
+    /// Proves that `z` is what `operation` gives for every `x` and `y`, the other inputs keeping
+    /// their initial values, or gives a pair for which it isn't. Each `z` wire and each bit of
+    /// the operation becomes a diagram whose variables are the bits of `x` and `y` interleaved,
+    /// which keeps adders small but not multipliers.
+    pub fn check_equivalence(
+        &self,
+        operation: &SimulatedOperation,
+    ) -> Result<Option<(BigUint, BigUint)>, EquivalenceError> {
+        self.check_equivalence_with(&self.connections, operation)
+    }
+
+    pub(super) fn check_equivalence_with(
+        &self,
+        connections: &[Connection<'_>],
+        operation: &SimulatedOperation,
+    ) -> Result<Option<(BigUint, BigUint)>, EquivalenceError> {
+        if let SimulatedOperation::Custom(_) = operation {
+            return Err(EquivalenceError::Opaque);
+        }
+
+        let circuit = Circuit::new(self.initial_values.keys().copied(), connections)?;
+        let [x, y, z] = ["x", "y", "z"].map(|prefix| circuit.wires_starting_with(prefix));
+
+        // bit i of x is the variable 2i, bit i of y the variable 2i + 1
+        let mut bdd = Bdd::new();
+        let mut inputs = circuit
+            .inputs()
+            .map(|wire| match self.initial_values[circuit.name(wire)] {
+                0 => FALSE,
+                _ => TRUE,
+            })
+            .collect::<Vec<_>>();
+        let [x_bits, y_bits] = [(&x, 0), (&y, 1)].map(|(wires, offset)| {
+            wires
+                .iter()
+                .enumerate()
+                .map(|(i, wire)| {
+                    inputs[wire.index()] = bdd.variable(2 * i + offset);
+                    inputs[wire.index()]
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let values =
+            circuit.evaluate_with(&inputs, |gate, left, right| bdd.apply(gate, *left, *right));
+        let expected = bdd.operation(operation, &x_bits, &y_bits, z.len())?;
+
+        for (wire, expected) in z.iter().zip(expected) {
+            let difference = bdd.apply(Gate::Xor, values[wire.index()], expected);
+            if let Some(ones) = bdd.satisfy(difference) {
+                let number = |bits: usize, offset| {
+                    BigUint::from_bits((0..bits).map(|i| ones.contains(&(2 * i + offset))))
+                };
+                return Ok(Some((number(x.len(), 0), number(y.len(), 1))));
+            }
+        }
+
+        Ok(None)
+    }
+}
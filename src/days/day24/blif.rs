@@ -0,0 +1,181 @@
+use std::fmt::Display;
+
+use crate::{ParseError, Source};
+
+use super::{Connection, Gate, MonitoringDevice};
+
+/// A [`MonitoringDevice`] in the Berkeley Logic Interchange Format: a model whose inputs are the
+/// wires with an initial value and whose outputs are the `z` wires, with a `.names` table per
+/// gate.
+pub struct Blif<'d, 'a> {
+    device: &'d MonitoringDevice<'a>,
+}
+
+impl<'a> MonitoringDevice<'a> {
+    pub fn blif(&self) -> Blif<'_, 'a> {
+        Blif { device: self }
+    }
+
+    /// Reads a model whose `.names` tables are the ones of `AND`, `OR`, `XOR`, `NAND`, `NOR`,
+    /// `XNOR` and `NOT` gates, whatever the rows they're written with. Inputs start at 0.
+    pub fn from_blif(input: &'a str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+        let mut lines = lines(input).into_iter().peekable();
+
+        let mut inputs = vec![];
+        let mut connections = vec![];
+        while let Some(line) = lines.next() {
+            match line[0] {
+                ".model" | ".outputs" => {}
+                ".inputs" => inputs.extend(&line[1..]),
+                ".end" => break,
+                ".names" => {
+                    let mut rows = vec![];
+                    while let Some(row) = lines.next_if(|line| !line[0].starts_with('.')) {
+                        rows.push(row);
+                    }
+                    connections.push(connection(&source, &line, &rows)?);
+                }
+                command => {
+                    return Err(source.error(
+                        command,
+                        "`.model`, `.inputs`, `.outputs`, `.names` or `.end`",
+                    ))
+                }
+            }
+        }
+
+        let device = Self::from_netlist(inputs, connections);
+        device.check(&source)?;
+        Ok(device)
+    }
+}
+
+/// The words of each line, lines ending with a backslash going on with the next one, without
+/// comments and empty lines.
+fn lines(input: &str) -> Vec<Vec<&str>> {
+    let mut lines = vec![];
+    let mut words = vec![];
+
+    for line in input.lines() {
+        let line = line.split_once('#').map_or(line, |(line, _)| line);
+        let (line, continued) = match line.trim_end().strip_suffix('\\') {
+            Some(line) => (line, true),
+            None => (line, false),
+        };
+
+        words.extend(line.split_whitespace());
+        if !continued && !words.is_empty() {
+            lines.push(std::mem::take(&mut words));
+        }
+    }
+    if !words.is_empty() {
+        lines.push(words);
+    }
+
+    lines
+}
+
+/// The gate whose table `rows` give, reading the wires listed after `.names`.
+fn connection<'a>(
+    source: &Source,
+    names: &[&'a str],
+    rows: &[Vec<&str>],
+) -> Result<Connection<'a>, ParseError> {
+    let command = names[0];
+    let Some((&result, inputs)) = names[1..].split_last() else {
+        return Err(source.error(command, "a gate with one or two inputs"));
+    };
+    if !(1..=2).contains(&inputs.len()) {
+        return Err(source.error(command, "a gate with one or two inputs"));
+    }
+
+    // the combinations of inputs the rows cover, the first input being the least significant bit
+    let mut covered = 0u64;
+    let mut value = None;
+    for row in rows {
+        let &[pattern, output] = &row[..] else {
+            return Err(source.error(row[0], "inputs and an output"));
+        };
+        if pattern.len() != inputs.len() || !pattern.chars().all(|c| "01-".contains(c)) {
+            return Err(source.error(pattern, "`0`, `1` or `-` for each input"));
+        }
+        if !["0", "1"].contains(&output) || value.is_some_and(|value| value != output) {
+            return Err(source.error(output, "the same output, `0` or `1`, on every row"));
+        }
+        value = Some(output);
+
+        for combination in 0..1 << inputs.len() {
+            let matches = pattern.chars().enumerate().all(|(i, c)| match c {
+                '0' => combination >> i & 1 == 0,
+                '1' => combination >> i & 1 == 1,
+                _ => true,
+            });
+            if matches {
+                covered |= 1 << combination;
+            }
+        }
+    }
+
+    let table = match value {
+        Some("0") => !covered & ((1 << (1 << inputs.len())) - 1),
+        _ => covered,
+    };
+    let gate = Gate::ALL
+        .into_iter()
+        .find(|gate| gate.arity() == inputs.len() && gate.truth_table() == table)
+        .ok_or_else(|| {
+            source.error(
+                command,
+                "the table of an AND, OR, XOR, NAND, NOR, XNOR or NOT gate",
+            )
+        })?;
+
+    Ok(match *inputs {
+        [input] => Connection::not(input, result),
+        [left, right] => Connection {
+            left,
+            right,
+            gate,
+            result,
+        },
+        _ => unreachable!("gates to have one or two inputs"),
+    })
+}
+
+impl Gate {
+    /// The rows of its `.names` table, the first input being on the left.
+    fn cover(&self) -> &'static [&'static str] {
+        match self {
+            Gate::And => &["11 1"],
+            Gate::Or => &["1- 1", "-1 1"],
+            Gate::Xor => &["10 1", "01 1"],
+            Gate::Nand => &["0- 1", "-0 1"],
+            Gate::Nor => &["00 1"],
+            Gate::Xnor => &["00 1", "11 1"],
+            Gate::Not => &["0 1"],
+        }
+    }
+}
+
+impl Display for Blif<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [inputs, outputs, _] = self.device.ports();
+
+        writeln!(f, ".model circuit")?;
+        writeln!(f, ".inputs {}", inputs.join(" "))?;
+        writeln!(f, ".outputs {}", outputs.join(" "))?;
+        for connection in &self.device.connections {
+            let wires = connection
+                .inputs()
+                .chain([connection.result])
+                .collect::<Vec<_>>();
+            writeln!(f, ".names {}", wires.join(" "))?;
+            for row in connection.gate.cover() {
+                writeln!(f, "{}", row)?;
+            }
+        }
+
+        writeln!(f, ".end")
+    }
+}
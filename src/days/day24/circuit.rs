@@ -0,0 +1,254 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+};
+
+use super::{BigUint, Connection, Gate};
+
+/// A wire of a [`Circuit`], standing for its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WireId(usize);
+
+impl WireId {
+    /// Where the wire is in the values [`Circuit::evaluate`] gives.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    /// The output wires of gates that depend on each other, each feeding the next one and the last
+    /// one the first.
+    Loop(Vec<String>),
+    /// A wire is the output of several gates, or of a gate while having an initial value.
+    MultipleDrivers(String),
+    /// A gate reads a wire that no gate drives and that has no initial value.
+    MissingInput(String),
+}
+
+impl Display for CircuitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitError::Loop(wires) => {
+                write!(f, "combinational loop through {}", wires.join(" -> "))
+            }
+            CircuitError::MultipleDrivers(wire) => write!(f, "{} is driven more than once", wire),
+            CircuitError::MissingInput(wire) => write!(f, "{} is never driven", wire),
+        }
+    }
+}
+
+impl std::error::Error for CircuitError {}
+
+#[derive(Debug, Clone)]
+struct CompiledGate {
+    gate: Gate,
+    left: WireId,
+    right: WireId,
+    output: WireId,
+}
+
+/// Gates sorted so that each one comes after the gates driving its inputs, wires being numbered
+/// instead of named. The inputs are numbered first, in the order of their names.
+#[derive(Debug, Clone)]
+pub struct Circuit<'a> {
+    names: Vec<&'a str>,
+    ids: HashMap<&'a str, WireId>,
+    inputs: usize,
+    gates: Vec<CompiledGate>,
+}
+
+impl<'a> Circuit<'a> {
+    pub(super) fn new(
+        inputs: impl IntoIterator<Item = &'a str>,
+        connections: &[Connection<'a>],
+    ) -> Result<Self, CircuitError> {
+        let mut names = inputs.into_iter().collect::<Vec<_>>();
+        names.sort_unstable();
+        names.dedup();
+        let inputs = names.len();
+
+        let mut ids = names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (*name, WireId(id)))
+            .collect::<HashMap<_, _>>();
+        let mut intern = |name: &'a str| {
+            *ids.entry(name).or_insert_with(|| {
+                names.push(name);
+                WireId(names.len() - 1)
+            })
+        };
+
+        let gates = connections
+            .iter()
+            .map(|connection| CompiledGate {
+                gate: connection.gate,
+                left: intern(connection.left),
+                right: intern(connection.right),
+                output: intern(connection.result),
+            })
+            .collect::<Vec<_>>();
+
+        let mut circuit = Self {
+            names,
+            ids,
+            inputs,
+            gates,
+        };
+        circuit.sort()?;
+        Ok(circuit)
+    }
+
+    /// Sorts the gates in topological order, in linear time.
+    fn sort(&mut self) -> Result<(), CircuitError> {
+        let mut drivers = vec![None; self.names.len()];
+        for (i, gate) in self.gates.iter().enumerate() {
+            if gate.output.0 < self.inputs || drivers[gate.output.0].is_some() {
+                return Err(CircuitError::MultipleDrivers(
+                    self.name(gate.output).to_string(),
+                ));
+            }
+            drivers[gate.output.0] = Some(i);
+        }
+
+        let mut readers = vec![vec![]; self.names.len()];
+        let mut missing_inputs = vec![0; self.gates.len()];
+        for (i, gate) in self.gates.iter().enumerate() {
+            for wire in [gate.left, gate.right] {
+                if wire.0 >= self.inputs && drivers[wire.0].is_none() {
+                    return Err(CircuitError::MissingInput(self.name(wire).to_string()));
+                }
+                if drivers[wire.0].is_some() {
+                    readers[wire.0].push(i);
+                    missing_inputs[i] += 1;
+                }
+            }
+        }
+
+        let mut ready = (0..self.gates.len())
+            .filter(|i| missing_inputs[*i] == 0)
+            .collect::<VecDeque<_>>();
+        let mut order = Vec::with_capacity(self.gates.len());
+        while let Some(i) = ready.pop_front() {
+            order.push(i);
+            for reader in &readers[self.gates[i].output.0] {
+                missing_inputs[*reader] -= 1;
+                if missing_inputs[*reader] == 0 {
+                    ready.push_back(*reader);
+                }
+            }
+        }
+
+        if order.len() < self.gates.len() {
+            return Err(self.find_loop(&drivers, &missing_inputs));
+        }
+
+        let mut gates = std::mem::take(&mut self.gates)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        self.gates = order
+            .into_iter()
+            .map(|i| gates[i].take().expect("gates to be sorted once"))
+            .collect();
+        Ok(())
+    }
+
+    /// The gates left unsorted each have an input driven by another one left unsorted, so going
+    /// from gate to driver among them ends up going round a loop.
+    fn find_loop(&self, drivers: &[Option<usize>], missing_inputs: &[usize]) -> CircuitError {
+        let unsorted = |wire: WireId| drivers[wire.0].filter(|driver| missing_inputs[*driver] > 0);
+
+        let mut path = vec![];
+        let mut position = HashMap::new();
+        let mut gate = (0..self.gates.len())
+            .find(|i| missing_inputs[*i] > 0)
+            .expect("a gate to be left unsorted");
+
+        while !position.contains_key(&gate) {
+            position.insert(gate, path.len());
+            path.push(gate);
+
+            let CompiledGate { left, right, .. } = self.gates[gate];
+            gate = unsorted(left)
+                .or_else(|| unsorted(right))
+                .expect("an unsorted gate to read from another one");
+        }
+
+        CircuitError::Loop(
+            path[position[&gate]..]
+                .iter()
+                .rev()
+                .map(|i| self.name(self.gates[*i].output).to_string())
+                .collect(),
+        )
+    }
+
+    pub fn wire(&self, name: &str) -> Option<WireId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, wire: WireId) -> &'a str {
+        self.names[wire.0]
+    }
+
+    pub fn wires(&self) -> impl Iterator<Item = WireId> + '_ {
+        (0..self.names.len()).map(WireId)
+    }
+
+    /// The wires without a driving gate, sorted by name.
+    pub fn inputs(&self) -> impl Iterator<Item = WireId> + '_ {
+        (0..self.inputs).map(WireId)
+    }
+
+    /// The wires whose name starts with `prefix`, in the order of the bits they stand for.
+    pub fn wires_starting_with(&self, prefix: &str) -> Vec<WireId> {
+        let mut wires = self
+            .wires()
+            .filter(|wire| self.name(*wire).starts_with(prefix))
+            .collect::<Vec<_>>();
+        wires.sort_unstable_by_key(|wire| bit_order(self.name(*wire)));
+        wires
+    }
+
+    /// The value of every wire, indexed by [`WireId`], for the values of the inputs in their order.
+    /// Values are words of 64 lanes, each bit being the value of the wire in an evaluation
+    /// independent from the ones in the other lanes.
+    pub fn evaluate(&self, inputs: &[u64]) -> Vec<u64> {
+        self.evaluate_with(inputs, |gate, left, right| gate.apply(*left, *right))
+    }
+
+    /// Like [`Circuit::evaluate`], for values of any kind that `apply` combines.
+    pub(super) fn evaluate_with<T: Clone + Default>(
+        &self,
+        inputs: &[T],
+        mut apply: impl FnMut(Gate, &T, &T) -> T,
+    ) -> Vec<T> {
+        assert_eq!(inputs.len(), self.inputs, "a value for each input");
+
+        let mut values = vec![T::default(); self.names.len()];
+        values[..self.inputs].clone_from_slice(inputs);
+        for gate in &self.gates {
+            values[gate.output.0] = apply(gate.gate, &values[gate.left.0], &values[gate.right.0]);
+        }
+
+        values
+    }
+
+    /// The number whose bits are the values in `lane` of the wires starting with `prefix`, the
+    /// first in the order of their names being the least significant.
+    pub fn value_of_wires(&self, values: &[u64], prefix: &str, lane: usize) -> BigUint {
+        BigUint::from_bits(
+            self.wires_starting_with(prefix)
+                .iter()
+                .map(|wire| values[wire.0] >> lane & 1 == 1),
+        )
+    }
+}
+
+/// Sorts `z100` after `z99`, the puzzle numbering bits with at least 2 digits.
+pub(super) fn bit_order(name: &str) -> (usize, &str) {
+    (name.len(), name)
+}
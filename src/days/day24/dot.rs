@@ -0,0 +1,156 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt::Display,
+};
+
+use super::{Gate, MonitoringDevice};
+
+/// A Graphviz rendering of a [`MonitoringDevice`]: inputs grouped by bit at the top, a node per
+/// gate named after the wire it drives and the `z` wires at the bottom.
+pub struct Dot<'d, 'a> {
+    device: &'d MonitoringDevice<'a>,
+    highlighted: HashSet<&'d str>,
+}
+
+impl<'a> MonitoringDevice<'a> {
+    pub fn dot(&self) -> Dot<'_, 'a> {
+        Dot {
+            device: self,
+            highlighted: HashSet::new(),
+        }
+    }
+}
+
+impl<'d> Dot<'d, '_> {
+    /// Draws these wires, and the gates driving them, in red.
+    pub fn highlight(mut self, wires: impl IntoIterator<Item = &'d str>) -> Self {
+        self.highlighted.extend(wires);
+        self
+    }
+
+    fn color(&self, wire: &str) -> &'static str {
+        if self.highlighted.contains(wire) {
+            ", color=red, fontcolor=red, penwidth=2"
+        } else {
+            ""
+        }
+    }
+}
+
+impl Gate {
+    fn shape(&self) -> &'static str {
+        match self {
+            Gate::And => "invhouse",
+            Gate::Or => "invtriangle",
+            Gate::Xor => "diamond",
+            Gate::Nand => "house",
+            Gate::Nor => "triangle",
+            Gate::Xnor => "Mdiamond",
+            Gate::Not => "circle",
+        }
+    }
+}
+
+impl Display for Dot<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let connections = &self.device.connections;
+        let driven = connections
+            .iter()
+            .map(|connection| connection.result)
+            .collect::<HashSet<_>>();
+        // the node a wire comes out of
+        let source = |wire: &str| {
+            if driven.contains(wire) {
+                format!("\"gate {}\"", wire)
+            } else {
+                format!("\"{}\"", wire)
+            }
+        };
+
+        writeln!(f, "digraph circuit {{")?;
+        writeln!(f, "    node [fontname=monospace];")?;
+
+        // x and y wires by bit, then the wires that are neither
+        let mut bits = BTreeMap::<&str, BTreeSet<&str>>::new();
+        let mut inputs = self.device.initial_values.keys().collect::<Vec<_>>();
+        inputs.sort_unstable();
+        for wire in inputs {
+            let bit = wire
+                .strip_prefix(['x', 'y'])
+                .filter(|bit| bit.chars().all(|c| c.is_ascii_digit()))
+                .unwrap_or("");
+            bits.entry(bit).or_default().insert(wire);
+        }
+        for (bit, wires) in &bits {
+            if bit.is_empty() {
+                for wire in wires {
+                    writeln!(f, "    \"{}\" [shape=box{}];", wire, self.color(wire))?;
+                }
+                continue;
+            }
+
+            writeln!(f, "    subgraph \"cluster bit {}\" {{", bit)?;
+            writeln!(f, "        label=\"bit {}\";", bit)?;
+            for wire in wires {
+                writeln!(f, "        \"{}\" [shape=box{}];", wire, self.color(wire))?;
+            }
+            writeln!(f, "    }}")?;
+        }
+
+        for connection in connections {
+            writeln!(
+                f,
+                "    \"gate {}\" [label=\"{}\\n{}\", shape={}{}];",
+                connection.result,
+                connection.gate,
+                connection.result,
+                connection.gate.shape(),
+                self.color(connection.result)
+            )?;
+            for input in connection.inputs() {
+                writeln!(
+                    f,
+                    "    {} -> \"gate {}\" [label=\"{}\"{}];",
+                    source(input),
+                    connection.result,
+                    input,
+                    self.color(input)
+                )?;
+            }
+        }
+
+        let mut outputs = connections
+            .iter()
+            .map(|connection| connection.result)
+            .filter(|wire| wire.starts_with('z'))
+            .collect::<Vec<_>>();
+        outputs.sort_unstable();
+        for wire in &outputs {
+            writeln!(f, "    \"{}\" [shape=box{}];", wire, self.color(wire))?;
+            writeln!(
+                f,
+                "    \"gate {}\" -> \"{}\"{};",
+                wire,
+                wire,
+                edge(self.color(wire))
+            )?;
+        }
+        if !outputs.is_empty() {
+            let outputs = outputs
+                .iter()
+                .map(|wire| format!("\"{}\"", wire))
+                .collect::<Vec<_>>();
+            writeln!(f, "    {{ rank=sink; {}; }}", outputs.join("; "))?;
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
+/// Edge attributes out of node ones, that start with a comma.
+fn edge(attributes: &str) -> String {
+    match attributes.strip_prefix(", ") {
+        Some(attributes) => format!(" [{}]", attributes),
+        None => String::new(),
+    }
+}
@@ -1,22 +1,150 @@
 // https://adventofcode.com/2024/day/24
 
-use std::collections::HashMap;
-
-pub fn solution_part1(input: &str) -> usize {
-    let device = MonitoringDevice::from_input(input);
-    device.z_output()
+pub use adder::{AdderError, AdderFault, AdderReport};
+pub use bdd::EquivalenceError;
+pub use blif::Blif;
+pub use circuit::{Circuit, CircuitError, WireId};
+pub use dot::Dot;
+pub use simulation::LANES;
+pub use verilog::Verilog;
+
+use std::{collections::HashMap, fmt::Display, str::FromStr, sync::Arc};
+
+use crate::{
+    big::BigUint,
+    solution::{Args, Outcome, Params, Solution},
+    ParseError, Source,
+};
+
+mod adder;
+mod bdd;
+mod blif;
+mod circuit;
+mod dot;
+mod simulation;
+mod verilog;
+
+pub fn solution_part1(input: &str) -> Result<BigUint, ParseError> {
+    let device = MonitoringDevice::from_input(input)?;
+    Ok(device.z_output())
 }
 
 pub fn solution_part2(
     input: &str,
     swapped: usize,
     simulated_operation: SimulatedOperation,
-) -> String {
-    let device = MonitoringDevice::from_input(input);
-    device.swapped_to_sum_x_y_equals_z(swapped, simulated_operation)
+) -> Result<String, PuzzleError> {
+    let device = MonitoringDevice::from_input(input)?;
+    Ok(device.swapped_to_sum_x_y_equals_z(swapped, &simulated_operation)?)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+
+    type Parsed<'a> = MonitoringDevice<'a>;
+    type Params = Repair;
+    type Answer1 = BigUint;
+    type Answer2 = Outcome<String, RepairError>;
+
+    fn parse(input: &str) -> Result<MonitoringDevice<'_>, ParseError> {
+        MonitoringDevice::from_input(input)
+    }
+
+    fn part1(device: MonitoringDevice<'_>, _: &Repair) -> BigUint {
+        device.z_output()
+    }
+
+    fn part2(device: MonitoringDevice<'_>, repair: &Repair) -> Outcome<String, RepairError> {
+        Outcome(device.swapped_to_sum_x_y_equals_z(repair.swapped, &repair.simulated_operation))
+    }
+}
+
+pub struct Repair {
+    pub swapped: usize,
+    pub simulated_operation: SimulatedOperation,
+}
+
+impl Default for Repair {
+    fn default() -> Self {
+        Self {
+            swapped: 4,
+            simulated_operation: SimulatedOperation::Sum,
+        }
+    }
+}
+
+impl Params for Repair {
+    fn from_args(args: &mut Args) -> Result<Self, String> {
+        let default = Self::default();
+
+        Ok(Self {
+            swapped: args.next_or("swapped", default.swapped)?,
+            simulated_operation: args.next_or("operation", default.simulated_operation)?,
+        })
+    }
+}
+
+/// Why no set of swapped outputs repairs the circuit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepairError {
+    /// Every candidate was ruled out.
+    NoSolution,
+    /// The circuit has too many gates to try every set of swaps.
+    TooManyCandidates,
+    /// That many candidates compute the operation, on every input or on every one they were
+    /// tried on when it can't be proven.
+    Ambiguous(usize),
+}
+
+impl Display for RepairError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepairError::NoSolution => write!(f, "no swaps make the circuit compute the operation"),
+            RepairError::TooManyCandidates => write!(f, "too many sets of swaps to try them all"),
+            RepairError::Ambiguous(candidates) => write!(
+                f,
+                "{} sets of swaps make the circuit compute the operation",
+                candidates
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RepairError {}
+
+/// Why [`solution_part2`] has no answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleError {
+    Parse(ParseError),
+    Repair(RepairError),
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PuzzleError::Parse(error) => write!(f, "invalid input at {}", error),
+            PuzzleError::Repair(error) => write!(f, "{}", error),
+        }
+    }
 }
 
-struct MonitoringDevice<'a> {
+impl std::error::Error for PuzzleError {}
+
+impl From<ParseError> for PuzzleError {
+    fn from(error: ParseError) -> Self {
+        PuzzleError::Parse(error)
+    }
+}
+
+impl From<RepairError> for PuzzleError {
+    fn from(error: RepairError) -> Self {
+        PuzzleError::Repair(error)
+    }
+}
+
+pub struct MonitoringDevice<'a> {
     initial_values: HashMap<&'a str, u32>,
     connections: Vec<Connection<'a>>,
 }
@@ -29,17 +157,38 @@ struct Connection<'a> {
     result: &'a str,
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 enum Gate {
     And,
     Or,
     Xor,
+    Nand,
+    Nor,
+    Xnor,
+    /// Reads only `left`, `right` being the same wire.
+    Not,
 }
 
-#[derive(Debug)]
+/// How many sets of swaps the brute force tries at most, as a real input has far too many.
+const MAX_CANDIDATES: u128 = 1_000_000;
+
+/// How many times [`LANES`] random inputs the candidate repairs are tried on.
+const SAMPLE_ROUNDS: u64 = 64;
+
+/// An operation on the values of wires that can be shared between threads.
+pub type Operation = Arc<dyn Fn(&BigUint, &BigUint) -> BigUint + Send + Sync>;
+
+/// What the circuit should compute out of `x` and `y`, the result being cut to the width of `z`.
+#[derive(Clone)]
 pub enum SimulatedOperation {
     Sum,
     And,
+    /// `x - y`, wrapping around like two's complement.
+    Difference,
+    Product,
+    /// 1 if `x < y`, else 0.
+    LessThan,
+    Custom(Operation),
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
@@ -72,31 +221,38 @@ impl Pair {
 }
 
 impl<'a> MonitoringDevice<'a> {
-    fn from_input(input: &'a str) -> Self {
+    pub fn from_input(input: &'a str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
         let lines = input.lines().collect::<Vec<&'a str>>();
 
-        let (initial_values_lines, connections_lines) = lines.split_once(|l| *l == "").unwrap();
+        let Some((initial_values_lines, connections_lines)) = lines.split_once(|l| l.is_empty())
+        else {
+            return Err(source.end("an empty line between wires and gates"));
+        };
 
         let initial_values = initial_values_lines
             .iter()
             .map(|l| {
-                let (name, value) = l.split_once(": ").unwrap();
-                (name, value.parse::<u32>().unwrap())
+                let (name, value) = source.split_once(l, ": ")?;
+                match value {
+                    "0" | "1" => Ok((name, source.number::<u32>(value)?)),
+                    _ => Err(source.error(value, "`0` or `1`")),
+                }
             })
-            .collect::<HashMap<_, _>>();
+            .collect::<Result<HashMap<_, _>, _>>()?;
 
         let connections = connections_lines
             .iter()
             .map(|l| {
-                let (operation, result) = l.split_once(" -> ").unwrap();
-                let mut operation_parts = operation.splitn(3, " ");
-                let (left, gate, right) = (
-                    operation_parts.next().unwrap(),
-                    operation_parts.next().unwrap(),
-                    operation_parts.next().unwrap(),
-                );
+                let (operation, result) = source.split_once(l, " -> ")?;
+                if let Some(input) = operation.strip_prefix("NOT ") {
+                    return Ok(Connection::not(input, result));
+                }
+
+                let (left, gate_and_right) = source.split_once(operation, " ")?;
+                let (gate, right) = source.split_once(gate_and_right, " ")?;
 
-                Connection {
+                Ok(Connection {
                     left,
                     right,
                     result,
@@ -104,33 +260,169 @@ impl<'a> MonitoringDevice<'a> {
                         "AND" => Gate::And,
                         "OR" => Gate::Or,
                         "XOR" => Gate::Xor,
-                        _ => panic!("invalid"),
+                        "NAND" => Gate::Nand,
+                        "NOR" => Gate::Nor,
+                        "XNOR" => Gate::Xnor,
+                        _ => {
+                            return Err(
+                                source.error(gate, "`AND`, `OR`, `XOR`, `NAND`, `NOR` or `XNOR`")
+                            )
+                        }
                     },
-                }
+                })
             })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let device = Self {
+            initial_values,
+            connections,
+        };
+        device.check(&source)?;
+        Ok(device)
+    }
+
+    /// Makes sure the circuit compiles, pointing at the gate that keeps it from doing so, the
+    /// wires being slices of the parsed input.
+    fn check(&self, source: &Source) -> Result<(), ParseError> {
+        let Err(error) = self.compile() else {
+            return Ok(());
+        };
+
+        let driving = |wire: &str| {
+            self.connections
+                .iter()
+                .map(|connection| connection.result)
+                .filter(|result| *result == wire)
+                .collect::<Vec<_>>()
+        };
+        Err(match error {
+            CircuitError::Loop(wires) => source.error(
+                driving(&wires[0])[0],
+                format!(
+                    "a gate outside the combinational loop through {}",
+                    wires.join(" -> ")
+                ),
+            ),
+            CircuitError::MultipleDrivers(wire) => {
+                let skip = if self.initial_values.contains_key(wire.as_str()) {
+                    0
+                } else {
+                    1
+                };
+                source.error(driving(&wire)[skip], "a wire that isn't driven yet")
+            }
+            CircuitError::MissingInput(wire) => {
+                let input = self
+                    .connections
+                    .iter()
+                    .flat_map(|connection| connection.inputs())
+                    .find(|input| *input == wire)
+                    .expect("the wire to be read");
+                source.error(input, "a wire with an initial value or a gate driving it")
+            }
+        })
+    }
+
+    /// How many `z` wires there are, that is the width of the output.
+    fn z_width(&self) -> usize {
+        let mut wires = self
+            .connections
+            .iter()
+            .map(|connection| connection.result)
+            .chain(self.initial_values.keys().copied())
+            .filter(|wire| wire.starts_with('z'))
             .collect::<Vec<_>>();
+        wires.sort_unstable();
+        wires.dedup();
+        wires.len()
+    }
 
+    /// Swaps the outputs of the gates driving `a` and `b`, like the repairs of part 2.
+    pub fn swap_outputs(&mut self, a: &str, b: &str) -> Result<(), CircuitError> {
+        let driver = |wire: &str| {
+            self.connections
+                .iter()
+                .position(|connection| connection.result == wire)
+                .ok_or_else(|| CircuitError::MissingInput(wire.to_string()))
+        };
+        let (i, j) = (driver(a)?, driver(b)?);
+
+        let swap = |connections: &mut [Connection<'a>]| {
+            let result = connections[i].result;
+            connections[i].result = connections[j].result;
+            connections[j].result = result;
+        };
+        swap(&mut self.connections);
+        // a swap can close a loop, which is undone to keep the device compiling
+        if let Err(error) = self.compile() {
+            swap(&mut self.connections);
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// The wires with an initial value, the `z` wires that gates drive and the other wires that
+    /// gates drive, each sorted by bit, as netlists declare them.
+    fn ports(&self) -> [Vec<&'a str>; 3] {
+        let mut inputs = self.initial_values.keys().copied().collect::<Vec<_>>();
+        let (mut outputs, mut wires): (Vec<_>, Vec<_>) = self
+            .connections
+            .iter()
+            .map(|connection| connection.result)
+            .partition(|wire| wire.starts_with('z'));
+
+        for wires in [&mut inputs, &mut outputs, &mut wires] {
+            wires.sort_unstable_by_key(|wire| circuit::bit_order(wire));
+        }
+        [inputs, outputs, wires]
+    }
+
+    /// A device reading `inputs`, all starting at 0, as netlists don't give them values. It is
+    /// left to the caller to [`check`](Self::check) it.
+    fn from_netlist(inputs: Vec<&'a str>, connections: Vec<Connection<'a>>) -> Self {
         Self {
-            initial_values,
+            initial_values: inputs.into_iter().map(|wire| (wire, 0)).collect(),
             connections,
         }
     }
 
-    fn z_output(&self) -> usize {
-        self.z_output_internal(self.initial_values.clone(), &self.connections)
+    /// The gates in evaluation order, the wires with an initial value being its inputs.
+    pub fn compile(&self) -> Result<Circuit<'a>, CircuitError> {
+        Circuit::new(self.initial_values.keys().copied(), &self.connections)
+    }
+
+    fn z_output(&self) -> BigUint {
+        self.z_output_internal(&self.initial_values, &self.connections)
+            .expect("devices to compile since they were parsed")
     }
 
     fn swapped_to_sum_x_y_equals_z(
         &self,
         swapped: usize,
-        simulated_operation: SimulatedOperation,
-    ) -> String {
-        let x: usize = calculate_value_of_wires(&self.initial_values, "x");
-        let y: usize = calculate_value_of_wires(&self.initial_values, "y");
-        let expected_z = simulated_operation.apply(x, y);
+        simulated_operation: &SimulatedOperation,
+    ) -> Result<String, RepairError> {
+        // adders are checked gate by gate, other operations need a brute force
+        if let SimulatedOperation::Sum = simulated_operation {
+            match self.check_adder() {
+                Ok(report) if report.swaps.len() == swapped && self.repairs_adder(&report) => {
+                    return Ok(report.swapped_wires().join(","))
+                }
+                _ => {}
+            }
+        }
+
+        let x = calculate_value_of_wires(&self.initial_values, "x");
+        let y = calculate_value_of_wires(&self.initial_values, "y");
+        let expected_z = simulated_operation.apply(&x, &y, self.z_width());
 
         let mut connections = self.connections.clone();
         let len = self.connections.len();
+        let candidates = u32::try_from(swapped).ok().and_then(|swapped| {
+            (len as u128 * len.saturating_sub(1) as u128 / 2).checked_pow(swapped)
+        });
+        if !candidates.is_some_and(|candidates| candidates <= MAX_CANDIDATES) {
+            return Err(RepairError::TooManyCandidates);
+        }
 
         let mut pairs = vec![Pair::new(); swapped];
         let mut maybe_pairs: HashMap<Vec<Pair>, bool> = HashMap::new();
@@ -163,7 +455,7 @@ impl<'a> MonitoringDevice<'a> {
                 connections[pair.1].result = temp;
             }
 
-            let z = self.z_output_internal(self.initial_values.clone(), &connections);
+            let z = self.z_output_internal(&self.initial_values, &connections);
 
             for pair in pairs.iter().rev() {
                 let temp = connections[pair.0].result;
@@ -171,152 +463,297 @@ impl<'a> MonitoringDevice<'a> {
                 connections[pair.1].result = temp;
             }
 
-            if expected_z == z {
+            if z.as_ref() == Ok(&expected_z) {
                 maybe_pairs.insert(pairs_to_save, true);
             } else {
                 maybe_pairs.insert(pairs_to_save, false);
             }
         }
 
-        let input_bits = self
-            .initial_values
-            .iter()
-            .filter(|(k, _)| k.starts_with("x"))
-            .count();
-
-        let maybe_pairs = maybe_pairs.into_iter().map(|(k, _)| k).collect::<Vec<_>>();
+        // the candidates that give the wrong `z` on the initial values are ruled out already
+        let maybe_pairs = maybe_pairs
+            .into_iter()
+            .filter_map(|(pairs, matches)| matches.then_some(pairs))
+            .collect::<Vec<_>>();
 
-        self.find_pairs_to_swap(simulated_operation, connections, input_bits, maybe_pairs)
+        self.find_pairs_to_swap(simulated_operation, connections, maybe_pairs)
     }
 
+    /// The single candidate that computes the operation: proven to for every input when the
+    /// operation can be turned into diagrams, else the one left after trying them on random
+    /// inputs of any width.
     fn find_pairs_to_swap(
         &self,
-        simulated_operation: SimulatedOperation,
+        simulated_operation: &SimulatedOperation,
         mut connections: Vec<Connection<'a>>,
-        input_bits: usize,
-        mut maybe_pairs: Vec<Vec<Pair>>,
-    ) -> String {
-        for x in 0..2usize.pow(input_bits as u32) {
-            for y in 0..2usize.pow(input_bits as u32) {
-                for i in (0..maybe_pairs.len()).rev() {
-                    for pair in maybe_pairs[i].iter() {
-                        let temp = connections[pair.0].result;
-                        connections[pair.0].result = connections[pair.1].result;
-                        connections[pair.1].result = temp;
-                    }
-
-                    let mut values = self.initial_values.clone();
-                    for xi in 0..input_bits {
-                        *values.get_mut(format!("x{:0>2}", xi).as_str()).unwrap() =
-                            if x & (1 << xi) != 0 { 1 } else { 0 };
-                    }
-                    for yi in 0..input_bits {
-                        *values.get_mut(format!("y{:0>2}", yi).as_str()).unwrap() =
-                            if y & (1 << yi) != 0 { 1 } else { 0 };
-                    }
-
-                    let z = self.z_output_internal(values, &connections);
-
-                    for pair in maybe_pairs[i].iter().rev() {
-                        let temp = connections[pair.0].result;
-                        connections[pair.0].result = connections[pair.1].result;
-                        connections[pair.1].result = temp;
-                    }
-
-                    if simulated_operation.apply(x, y) != z {
-                        maybe_pairs.remove(i);
-                    }
-                }
+        maybe_pairs: Vec<Vec<Pair>>,
+    ) -> Result<String, RepairError> {
+        let maybe_pairs =
+            match self.equivalent_swaps(simulated_operation, &mut connections, &maybe_pairs) {
+                Some(equivalent) => equivalent,
+                None => self.sampled_swaps(simulated_operation, &mut connections, maybe_pairs),
+            };
 
-                if maybe_pairs.len() == 1 {
-                    let mut connections_to_swap = maybe_pairs[0]
-                        .iter()
-                        .flat_map(|pair| {
-                            vec![connections[pair.0].result, connections[pair.1].result]
-                        })
-                        .collect::<Vec<_>>();
-                    connections_to_swap.sort();
-                    return connections_to_swap.join(",");
-                } else if maybe_pairs.is_empty() {
-                    unreachable!("Removed everything");
-                }
+        match &maybe_pairs[..] {
+            [] => Err(RepairError::NoSolution),
+            [pairs] => {
+                let mut connections_to_swap = pairs
+                    .iter()
+                    .flat_map(|pair| [connections[pair.0].result, connections[pair.1].result])
+                    .collect::<Vec<_>>();
+                connections_to_swap.sort();
+                Ok(connections_to_swap.join(","))
             }
+            _ => Err(RepairError::Ambiguous(maybe_pairs.len())),
         }
-
-        unreachable!("Not found, remaining {:?}", maybe_pairs);
     }
 
-    fn z_output_internal(
+    /// The candidates that give the same `z` as the operation on random inputs, stopping as soon
+    /// as a single one is left.
+    fn sampled_swaps(
         &self,
-        mut values: HashMap<&'a str, u32>,
-        connections: &Vec<Connection<'a>>,
-    ) -> usize {
-        let mut connections_to_try = vec![];
-
-        for connection in connections {
-            let (Some(left), Some(right)) =
-                (values.get(connection.left), values.get(connection.right))
-            else {
-                connections_to_try.push(connection);
-                continue;
-            };
+        simulated_operation: &SimulatedOperation,
+        connections: &mut [Connection<'a>],
+        mut maybe_pairs: Vec<Vec<Pair>>,
+    ) -> Vec<Vec<Pair>> {
+        let z_width = self.z_width();
+        for seed in 0..SAMPLE_ROUNDS {
+            if maybe_pairs.len() <= 1 {
+                break;
+            }
 
-            values.insert(connection.result, connection.gate.apply(*left, *right));
-        }
+            let pairs = self.random_pairs(LANES, seed);
+            let expected_z = pairs
+                .iter()
+                .map(|(x, y)| simulated_operation.apply(x, y, z_width))
+                .collect::<Vec<_>>();
+
+            maybe_pairs.retain(|candidate| {
+                for pair in candidate.iter() {
+                    let temp = connections[pair.0].result;
+                    connections[pair.0].result = connections[pair.1].result;
+                    connections[pair.1].result = temp;
+                }
 
-        let mut a_change_happened = true;
+                let z = self.simulate_with(connections, &pairs);
 
-        while !connections_to_try.is_empty() && a_change_happened {
-            a_change_happened = false;
+                for pair in candidate.iter().rev() {
+                    let temp = connections[pair.0].result;
+                    connections[pair.0].result = connections[pair.1].result;
+                    connections[pair.1].result = temp;
+                }
 
-            let connections = connections_to_try;
-            connections_to_try = vec![];
+                z.as_ref() == Ok(&expected_z)
+            });
+        }
 
-            for connection in connections {
-                let (Some(left), Some(right)) =
-                    (values.get(connection.left), values.get(connection.right))
-                else {
-                    connections_to_try.push(connection);
-                    continue;
-                };
+        maybe_pairs
+    }
 
-                values.insert(connection.result, connection.gate.apply(*left, *right));
-                a_change_happened = true;
-            }
+    /// The candidates that are proven to make the circuit compute the operation for every input,
+    /// `None` if the operation can only be sampled. The counterexamples of the candidates ruled
+    /// out are simulated on the next ones first, which usually rules them out without building
+    /// diagrams.
+    fn equivalent_swaps(
+        &self,
+        simulated_operation: &SimulatedOperation,
+        connections: &mut [Connection<'a>],
+        candidates: &[Vec<Pair>],
+    ) -> Option<Vec<Vec<Pair>>> {
+        if let SimulatedOperation::Custom(_) = simulated_operation {
+            return None;
         }
 
-        calculate_value_of_wires(&values, "z")
+        let swap = |connections: &mut [Connection<'a>], pair: &Pair| {
+            let temp = connections[pair.0].result;
+            connections[pair.0].result = connections[pair.1].result;
+            connections[pair.1].result = temp;
+        };
+        let z_width = self.z_width();
+        let mut counterexamples = vec![];
+        let mut expected_z = vec![];
+        let equivalent = candidates
+            .iter()
+            .filter(|pairs| {
+                pairs.iter().for_each(|pair| swap(connections, pair));
+                let refuted = !counterexamples.is_empty()
+                    && self.simulate_with(connections, &counterexamples).as_ref()
+                        != Ok(&expected_z);
+                let equivalent = !refuted
+                    && match self.check_equivalence_with(connections, simulated_operation) {
+                        Ok(None) => true,
+                        Ok(Some((x, y))) => {
+                            // one word of lanes keeps the simulation a single evaluation
+                            if counterexamples.len() < LANES {
+                                expected_z.push(simulated_operation.apply(&x, &y, z_width));
+                                counterexamples.push((x, y));
+                            }
+                            false
+                        }
+                        Err(_) => false,
+                    };
+                pairs.iter().rev().for_each(|pair| swap(connections, pair));
+                equivalent
+            })
+            .cloned()
+            .collect();
+        Some(equivalent)
+    }
+
+    /// Swapped wires may well make loops, that end up as errors.
+    fn z_output_internal(
+        &self,
+        values: &HashMap<&'a str, u32>,
+        connections: &[Connection<'a>],
+    ) -> Result<BigUint, CircuitError> {
+        let circuit = Circuit::new(values.keys().copied(), connections)?;
+        let inputs = circuit
+            .inputs()
+            .map(|wire| values[circuit.name(wire)] as u64)
+            .collect::<Vec<_>>();
+
+        Ok(circuit.value_of_wires(&circuit.evaluate(&inputs), "z", 0))
     }
 }
 
-fn calculate_value_of_wires(values: &HashMap<&str, u32>, starts_with: &str) -> usize {
+fn calculate_value_of_wires(values: &HashMap<&str, u32>, starts_with: &str) -> BigUint {
     let mut value = values
-        .into_iter()
+        .iter()
         .filter(|(k, _)| k.starts_with(starts_with))
         .collect::<Vec<_>>();
-    value.sort_by_key(|v| v.0);
+    value.sort_by_key(|v| circuit::bit_order(v.0));
+
+    BigUint::from_bits(value.into_iter().map(|(_, v)| *v == 1))
+}
 
-    value
-        .into_iter()
-        .rev()
-        .fold(0usize, |output, (_, v)| (output << 1) + *v as usize)
+impl<'a> Connection<'a> {
+    fn not(input: &'a str, result: &'a str) -> Self {
+        Self {
+            left: input,
+            right: input,
+            gate: Gate::Not,
+            result,
+        }
+    }
+
+    /// The wires the gate reads, once each for a `NOT`.
+    fn inputs(&self) -> impl Iterator<Item = &'a str> {
+        [self.left, self.right].into_iter().take(self.gate.arity())
+    }
+}
+
+impl Display for Connection<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.gate {
+            Gate::Not => write!(f, "NOT {} -> {}", self.left, self.result),
+            _ => write!(
+                f,
+                "{} {} {} -> {}",
+                self.left, self.gate, self.right, self.result
+            ),
+        }
+    }
+}
+
+impl Display for Gate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Gate::And => "AND",
+            Gate::Or => "OR",
+            Gate::Xor => "XOR",
+            Gate::Nand => "NAND",
+            Gate::Nor => "NOR",
+            Gate::Xnor => "XNOR",
+            Gate::Not => "NOT",
+        })
+    }
 }
 
 impl Gate {
-    fn apply(&self, left: u32, right: u32) -> u32 {
+    const ALL: [Gate; 7] = [
+        Gate::And,
+        Gate::Or,
+        Gate::Xor,
+        Gate::Nand,
+        Gate::Nor,
+        Gate::Xnor,
+        Gate::Not,
+    ];
+
+    fn arity(&self) -> usize {
+        match self {
+            Gate::Not => 1,
+            _ => 2,
+        }
+    }
+
+    /// Bitwise, so that each bit of the words is an independent evaluation.
+    fn apply(&self, left: u64, right: u64) -> u64 {
         match self {
             Gate::And => left & right,
             Gate::Or => left | right,
             Gate::Xor => left ^ right,
+            Gate::Nand => !(left & right),
+            Gate::Nor => !(left | right),
+            Gate::Xnor => !(left ^ right),
+            Gate::Not => !left,
+        }
+    }
+
+    /// The output for each combination of inputs, the first input being the least significant
+    /// bit of the combination.
+    fn truth_table(&self) -> u64 {
+        let combinations = 1 << self.arity();
+        self.apply(0b1010, 0b1100) & ((1 << combinations) - 1)
+    }
+}
+
+impl FromStr for SimulatedOperation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sum" => Ok(SimulatedOperation::Sum),
+            "and" => Ok(SimulatedOperation::And),
+            "sub" => Ok(SimulatedOperation::Difference),
+            "mul" => Ok(SimulatedOperation::Product),
+            "lt" => Ok(SimulatedOperation::LessThan),
+            _ => Err(format!("unknown simulated operation {}", s)),
         }
     }
 }
 
 impl SimulatedOperation {
-    fn apply(&self, left: usize, right: usize) -> usize {
+    pub fn custom(
+        operation: impl Fn(&BigUint, &BigUint) -> BigUint + Send + Sync + 'static,
+    ) -> Self {
+        SimulatedOperation::Custom(Arc::new(operation))
+    }
+
+    /// The expected value of the `width` wires of `z`.
+    pub fn apply(&self, left: &BigUint, right: &BigUint, width: usize) -> BigUint {
+        let result = match self {
+            SimulatedOperation::Sum => left.add(right),
+            SimulatedOperation::And => left.and(right),
+            SimulatedOperation::Difference => left.wrapping_sub(right, width),
+            SimulatedOperation::Product => left.mul(right),
+            SimulatedOperation::LessThan => BigUint::from((left < right) as u64),
+            SimulatedOperation::Custom(operation) => operation(left, right),
+        };
+
+        result.truncate(width)
+    }
+}
+
+impl std::fmt::Debug for SimulatedOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            SimulatedOperation::Sum => left + right,
-            SimulatedOperation::And => left & right,
+            SimulatedOperation::Sum => write!(f, "Sum"),
+            SimulatedOperation::And => write!(f, "And"),
+            SimulatedOperation::Difference => write!(f, "Difference"),
+            SimulatedOperation::Product => write!(f, "Product"),
+            SimulatedOperation::LessThan => write!(f, "LessThan"),
+            SimulatedOperation::Custom(_) => write!(f, "Custom(..)"),
         }
     }
 }
@@ -324,6 +761,7 @@ impl SimulatedOperation {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "x00: 1
 x01: 1
@@ -404,36 +842,629 @@ x03 AND y03 -> z03
 x04 AND y04 -> z04
 x05 AND y05 -> z00";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 4);
+        assert_eq!(solution_part1(EXAMPLE), Ok(BigUint::from(4)));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            solution_part1("x00: 1\ny00: 0\n\nx00 NOT y00 -> z00"),
+            Err(ParseError {
+                line: 4,
+                column: 5,
+                expected: "`AND`, `OR`, `XOR`, `NAND`, `NOR` or `XNOR`".to_string(),
+                found: Some("NOT".to_string()),
+            })
+        );
     }
 
     #[test]
     fn test_part1_example2() {
-        assert_eq!(solution_part1(EXAMPLE2), 2024);
+        assert_eq!(solution_part1(EXAMPLE2), Ok(BigUint::from(2024)));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 49520947122770);
+        let expected = real_answer!(24, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(24)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn test_compile() {
+        let device = MonitoringDevice::from_input(EXAMPLE2).unwrap();
+        let circuit = device.compile().unwrap();
+
+        let inputs = circuit
+            .inputs()
+            .map(|w| circuit.name(w))
+            .collect::<Vec<_>>();
+        assert_eq!(inputs[..3], ["x00", "x01", "x02"]);
+        assert_eq!(inputs.len(), 10);
+
+        let values = circuit.evaluate(&[1, 0, 1, 1, 0, 1, 1, 1, 1, 1]);
+        assert_eq!(circuit.value_of_wires(&values, "z", 0), BigUint::from(2024));
+        assert_eq!(values[circuit.wire("tnw").unwrap().index()], 1);
+        assert_eq!(circuit.wire("nope"), None);
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        let error = |input| {
+            let error = solution_part1(input).unwrap_err();
+            (error.line, error.column, error.expected)
+        };
+
+        assert_eq!(
+            error("x00: 1\n\nx00 AND b -> a\na OR x00 -> c\nc XOR x00 -> b"),
+            (
+                4,
+                13,
+                "a gate outside the combinational loop through c -> b -> a".to_string()
+            )
+        );
+        assert_eq!(
+            error("x00: 1\n\nx00 AND x00 -> z00\nx00 OR x00 -> z00"),
+            (4, 15, "a wire that isn't driven yet".to_string())
+        );
+        assert_eq!(
+            error("x00: 1\n\nx00 AND x00 -> x00"),
+            (3, 16, "a wire that isn't driven yet".to_string())
+        );
+        assert_eq!(
+            error("x00: 1\n\nx00 AND y00 -> z00"),
+            (
+                3,
+                9,
+                "a wire with an initial value or a gate driving it".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_simulate() {
+        let pair = |x, y| (BigUint::from(x), BigUint::from(y));
+
+        let device = MonitoringDevice::from_input(EXAMPLE2).unwrap();
+        assert_eq!(
+            device.simulate(&[pair(0b01101, 0b11111)]),
+            Ok(vec![BigUint::from(2024)])
+        );
+
+        // lanes don't interfere, across several words
+        let pairs = (0..100).map(|i| pair(i % 32, i / 4)).collect::<Vec<_>>();
+        let z = device.simulate(&pairs).unwrap();
+        assert_eq!(z.len(), 100);
+        assert_eq!(device.simulate(&pairs[70..71]), Ok(vec![z[70].clone()]));
+
+        let adder = ripple_carry_adder(100, &[]);
+        let device = MonitoringDevice::from_input(&adder).unwrap();
+        let big = BigUint::from_bits((0..100).map(|bit| bit % 3 == 0));
+        let pairs = [pair(0, 0), pair(u64::MAX, 1), (big.clone(), big.clone())];
+        assert_eq!(
+            device.simulate(&pairs),
+            Ok(pairs.iter().map(|(x, y)| x.add(y)).collect())
+        );
+        assert_eq!(device.simulate(&pairs).unwrap()[2].bit_length(), 101);
+        assert_eq!(
+            device.find_counterexample(&SimulatedOperation::Sum, 10_000, 24),
+            Ok(None)
+        );
+
+        let broken = ripple_carry_adder(100, &[("z70", "g70")]);
+        let device = MonitoringDevice::from_input(&broken).unwrap();
+        let (x, y) = device
+            .find_counterexample(&SimulatedOperation::Sum, 1000, 24)
+            .unwrap()
+            .unwrap();
+        assert_ne!(
+            device.simulate(&[(x.clone(), y.clone())]),
+            Ok(vec![x.add(&y)])
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let (two, three) = (BigUint::from(2), BigUint::from(3));
+        let apply = |operation: SimulatedOperation| operation.apply(&two, &three, 4);
+        assert_eq!(apply(SimulatedOperation::Sum), BigUint::from(5));
+        assert_eq!(apply(SimulatedOperation::And), BigUint::from(2));
+        assert_eq!(apply(SimulatedOperation::Difference), BigUint::from(15));
+        assert_eq!(apply(SimulatedOperation::Product), BigUint::from(6));
+        assert_eq!(apply(SimulatedOperation::LessThan), BigUint::from(1));
+        assert_eq!(
+            apply(SimulatedOperation::custom(|x, y| x.xor(y).add(&y.mul(y)))),
+            BigUint::from(10)
+        );
+        assert_eq!("lt".parse::<SimulatedOperation>().map(|_| ()), Ok(()));
+    }
+
+    #[test]
+    fn test_operations() {
+        // a 70-bit bitwise XOR
+        let mut lines = vec![];
+        for prefix in ["x", "y"] {
+            lines.extend((0..70).map(|bit| format!("{}{:02}: 0", prefix, bit)));
+        }
+        lines.push(String::new());
+        lines.extend((0..70).map(|bit| format!("x{0:02} XOR y{0:02} -> z{0:02}", bit)));
+        let xor = lines.join("\n");
+        let device = MonitoringDevice::from_input(&xor).unwrap();
+
+        let operation = SimulatedOperation::custom(|x, y| x.xor(y));
+        assert_eq!(device.find_counterexample(&operation, 1000, 1), Ok(None));
+        assert!(device
+            .find_counterexample(&SimulatedOperation::And, 1000, 1)
+            .unwrap()
+            .is_some());
+
+        // z is the width of x, so the difference wraps around
+        let adder = MonitoringDevice::from_input(EXAMPLE3).unwrap();
+        assert_eq!(adder.z_width(), 6);
+        assert_eq!(
+            SimulatedOperation::Difference.apply(
+                &BigUint::from(1),
+                &BigUint::from(2),
+                adder.z_width()
+            ),
+            BigUint::from(63)
+        );
+    }
+
+    #[test]
+    fn test_dot() {
+        let device = MonitoringDevice::from_input(
+            "x00: 1
+y00: 0
+enable: 1
+
+x00 AND y00 -> abc
+abc XOR enable -> z00",
+        )
+        .unwrap();
+
+        assert_eq!(
+            device.dot().highlight(["abc"]).to_string(),
+            r#"digraph circuit {
+    node [fontname=monospace];
+    "enable" [shape=box];
+    subgraph "cluster bit 00" {
+        label="bit 00";
+        "x00" [shape=box];
+        "y00" [shape=box];
+    }
+    "gate abc" [label="AND\nabc", shape=invhouse, color=red, fontcolor=red, penwidth=2];
+    "x00" -> "gate abc" [label="x00"];
+    "y00" -> "gate abc" [label="y00"];
+    "gate z00" [label="XOR\nz00", shape=diamond];
+    "gate abc" -> "gate z00" [label="abc", color=red, fontcolor=red, penwidth=2];
+    "enable" -> "gate z00" [label="enable"];
+    "z00" [shape=box];
+    "gate z00" -> "z00";
+    { rank=sink; "z00"; }
+}
+"#
+        );
+    }
+
+    const INVERTED: &str = "x00: 0
+y00: 0
+
+x00 NAND y00 -> and
+and NOR y00 -> z00
+x00 XNOR y00 -> z01
+NOT x00 -> z02";
+
+    #[test]
+    fn test_inverted_gates() {
+        assert_eq!(solution_part1(INVERTED), Ok(BigUint::from(0b110)));
+
+        let device = MonitoringDevice::from_input(INVERTED).unwrap();
+        assert_eq!(device.connections[3].to_string(), "NOT x00 -> z02");
+        let pairs =
+            [(0, 0), (0, 1), (1, 0), (1, 1)].map(|(x, y)| (BigUint::from(x), BigUint::from(y)));
+        assert_eq!(
+            device.simulate(&pairs),
+            Ok([0b110, 0b100, 0b000, 0b010].map(BigUint::from).to_vec())
+        );
+    }
+
+    #[test]
+    fn test_verilog() {
+        let device = MonitoringDevice::from_input(INVERTED).unwrap();
+        let verilog = device.verilog().to_string();
+        assert_eq!(
+            verilog,
+            r"module circuit(x00, y00, z00, z01, z02);
+    input x00, y00;
+    output z00, z01, z02;
+    wire \and ;
+
+    nand (\and , x00, y00);
+    nor (z00, \and , y00);
+    xnor (z01, x00, y00);
+    not (z02, x00);
+endmodule
+"
+        );
+
+        let pairs =
+            [(0, 0), (0, 1), (1, 0), (1, 1)].map(|(x, y)| (BigUint::from(x), BigUint::from(y)));
+        let imported = MonitoringDevice::from_verilog(&verilog).unwrap();
+        assert_eq!(imported.simulate(&pairs), device.simulate(&pairs));
+
+        let half_adder = "// a half adder
+module half_adder(x00, y00, z00, z01);
+  input x00,
+        y00;
+  output z00, z01;
+  xor sum (z00, x00, y00); /* the carry: */ and g2(z01, x00, y00);
+endmodule";
+        let imported = MonitoringDevice::from_verilog(half_adder).unwrap();
+        assert_eq!(
+            imported.check_adder().map(|report| report.is_adder()),
+            Ok(true)
+        );
+
+        assert_eq!(
+            MonitoringDevice::from_verilog(&half_adder.replace("y00);", "y00, x00);"))
+                .err()
+                .map(|error| (error.line, error.expected)),
+            Some((6, "two inputs".to_string()))
+        );
+        assert_eq!(
+            MonitoringDevice::from_verilog(&half_adder.replace("g2(z01, x00", "g2(z01, c00"))
+                .err()
+                .map(|error| (error.line, error.expected)),
+            Some((
+                6,
+                "a wire with an initial value or a gate driving it".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_blif() {
+        let device = MonitoringDevice::from_input(INVERTED).unwrap();
+        let blif = device.blif().to_string();
+        assert_eq!(
+            blif,
+            ".model circuit
+.inputs x00 y00
+.outputs z00 z01 z02
+.names x00 y00 and
+0- 1
+-0 1
+.names and y00 z00
+00 1
+.names x00 y00 z01
+00 1
+11 1
+.names x00 z02
+0 1
+.end
+"
+        );
+
+        let pairs =
+            [(0, 0), (0, 1), (1, 0), (1, 1)].map(|(x, y)| (BigUint::from(x), BigUint::from(y)));
+        let imported = MonitoringDevice::from_blif(&blif).unwrap();
+        assert_eq!(imported.simulate(&pairs), device.simulate(&pairs));
+
+        // tables given by the rows that are 0, or with overlapping rows
+        let written = ".model mine
+.inputs x00 \\
+    y00 # the second input
+.outputs z00 z01
+.names x00 y00 z00
+11 0
+.names y00 x00 z01
+1- 1
+11 1
+01 1
+.end";
+        let imported = MonitoringDevice::from_blif(written).unwrap();
+        assert_eq!(
+            imported
+                .connections
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["x00 NAND y00 -> z00", "y00 OR x00 -> z01"]
+        );
+
+        assert_eq!(
+            MonitoringDevice::from_blif(&written.replace("11 0", "10 1"))
+                .err()
+                .map(|error| (error.line, error.found)),
+            Some((5, Some(".names".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_swap_outputs() {
+        let input = ripple_carry_adder(4, &[("z02", "g02")]);
+        let mut device = MonitoringDevice::from_input(&input).unwrap();
+        assert!(!device.check_adder().unwrap().is_adder());
+
+        device.swap_outputs("g02", "z02").unwrap();
+        assert!(device.check_adder().unwrap().is_adder());
+        assert_eq!(
+            device.swap_outputs("p01", "c01"),
+            Err(CircuitError::Loop(vec![
+                "t01".to_string(),
+                "p01".to_string()
+            ]))
+        );
+        assert!(device.check_adder().unwrap().is_adder());
+        assert_eq!(
+            device.swap_outputs("x00", "z00"),
+            Err(CircuitError::MissingInput("x00".to_string()))
+        );
     }
 
     #[test]
     fn test_part2_example() {
         assert_eq!(
-            solution_part2(EXAMPLE3, 2, SimulatedOperation::And),
-            "z00,z01,z02,z05"
+            solution_part2(EXAMPLE3, 2, SimulatedOperation::And).as_deref(),
+            Ok("z00,z01,z02,z05")
+        );
+        assert_eq!(
+            solution_part2(EXAMPLE3, 1, SimulatedOperation::And),
+            Err(PuzzleError::Repair(RepairError::NoSolution))
+        );
+
+        // a product cut to 2 bits, with the outputs of the gates of the first bit swapped
+        let product = "x00: 1\nx01: 1\ny00: 1\ny01: 0
+
+x00 AND y01 -> z00
+x01 AND y00 -> a
+x00 AND y00 -> b
+a XOR b -> z01";
+        assert_eq!(
+            solution_part2(product, 1, SimulatedOperation::Product).as_deref(),
+            Ok("b,z00")
+        );
+    }
+
+    #[test]
+    fn test_part2_ambiguous() {
+        // swapping either pair of redundant gates keeps computing x AND y
+        let redundant = "x00: 1
+y00: 1
+x01: 0
+y01: 1
+
+a OR b -> z00
+x00 AND y00 -> a
+x00 AND y00 -> b
+x01 AND y01 -> c
+x01 AND y01 -> d
+c OR d -> z01";
+
+        for operation in [
+            SimulatedOperation::And,
+            SimulatedOperation::custom(|x, y| x.and(y)),
+        ] {
+            assert_eq!(
+                solution_part2(redundant, 1, operation),
+                Err(PuzzleError::Repair(RepairError::Ambiguous(2)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_part2_wide() {
+        // a 65-bit bitwise AND with the outputs of bits 3 and 64 swapped
+        let and = {
+            let mut lines = vec![];
+            for (prefix, period) in [("x", 3), ("y", 2)] {
+                lines.extend(
+                    (0..65)
+                        .map(|bit| format!("{}{:02}: {}", prefix, bit, (bit % period == 0) as u8)),
+                );
+            }
+            lines.push(String::new());
+            lines.extend((0..65).map(|bit| {
+                let z = match bit {
+                    3 => 64,
+                    64 => 3,
+                    _ => bit,
+                };
+                format!("x{0:02} AND y{0:02} -> z{1:02}", bit, z)
+            }));
+            lines.join("\n")
+        };
+        let custom = SimulatedOperation::custom(|x, y| x.and(y));
+
+        assert_eq!(
+            solution_part2(&and, 1, SimulatedOperation::And).as_deref(),
+            Ok("z03,z64")
         );
+        assert_eq!(solution_part2(&and, 1, custom).as_deref(), Ok("z03,z64"));
     }
 
-    #[ignore = "manually found by manually looking at the graph, didn't bother implementing it"]
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        // solution is gjc,gvm,qjj,qsb,wmp,z17,z26,z39
-        // manually found by manually looking at the graph, didn't bother implementing it
+        let expected = real_answer!(24, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(24), 4, SimulatedOperation::Sum)
+                .map(|answer| answer.to_string()),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn test_check_equivalence() {
+        let input = ripple_carry_adder(45, &[]);
+        let adder = MonitoringDevice::from_input(&input).unwrap();
+        assert_eq!(adder.check_equivalence(&SimulatedOperation::Sum), Ok(None));
+
+        let (x, y) = adder
+            .check_equivalence(&SimulatedOperation::Difference)
+            .unwrap()
+            .unwrap();
+        assert_ne!(
+            adder.simulate(&[(x.clone(), y.clone())]).unwrap()[0],
+            SimulatedOperation::Difference.apply(&x, &y, 46)
+        );
+
+        let input = ripple_carry_adder(8, &[("z03", "g03")]);
+        let swapped = MonitoringDevice::from_input(&input).unwrap();
+        let (x, y) = swapped
+            .check_equivalence(&SimulatedOperation::Sum)
+            .unwrap()
+            .unwrap();
+        assert_ne!(
+            swapped.simulate(&[(x.clone(), y.clone())]).unwrap()[0],
+            x.add(&y)
+        );
+
+        let less_than =
+            MonitoringDevice::from_input("x00: 0\ny00: 0\n\nNOT x00 -> nx\nnx AND y00 -> z00")
+                .unwrap();
+        assert_eq!(
+            less_than.check_equivalence(&SimulatedOperation::LessThan),
+            Ok(None)
+        );
+
+        // a product cut to 2 bits
+        let product = MonitoringDevice::from_input(
+            "x00: 0\nx01: 0\ny00: 0\ny01: 0
+
+x00 AND y00 -> z00
+x01 AND y00 -> a
+x00 AND y01 -> b
+a XOR b -> z01",
+        )
+        .unwrap();
+        assert_eq!(
+            product.check_equivalence(&SimulatedOperation::Product),
+            Ok(None)
+        );
+
+        assert_eq!(
+            product.check_equivalence(&SimulatedOperation::custom(|x, y| x.mul(y))),
+            Err(EquivalenceError::Opaque)
+        );
+    }
+
+    /// A ripple-carry adder of `bits` bits, the outputs of each pair of `swaps` being swapped.
+    fn ripple_carry_adder(bits: usize, swaps: &[(&str, &str)]) -> String {
+        let mut lines = vec![];
+        for prefix in ["x", "y"] {
+            lines.extend((0..bits).map(|bit| format!("{}{:02}: {}", prefix, bit, bit % 2)));
+        }
+        lines.push(String::new());
+
+        let carry = |bit| match bit {
+            _ if bit + 1 == bits => format!("z{:02}", bits),
+            _ => format!("c{:02}", bit),
+        };
+        for bit in 0..bits {
+            let (x, y, z) = (
+                format!("x{:02}", bit),
+                format!("y{:02}", bit),
+                format!("z{:02}", bit),
+            );
+            if bit == 0 {
+                lines.push(format!("{} XOR {} -> {}", x, y, z));
+                lines.push(format!("{} AND {} -> {}", y, x, carry(bit)));
+                continue;
+            }
+
+            let (p, g, t) = (
+                format!("p{:02}", bit),
+                format!("g{:02}", bit),
+                format!("t{:02}", bit),
+            );
+            lines.push(format!("{} XOR {} -> {}", x, y, p));
+            lines.push(format!("{} AND {} -> {}", x, y, g));
+            lines.push(format!("{} XOR {} -> {}", carry(bit - 1), p, z));
+            lines.push(format!("{} AND {} -> {}", p, carry(bit - 1), t));
+            lines.push(format!("{} OR {} -> {}", g, t, carry(bit)));
+        }
+
+        for line in lines.iter_mut() {
+            for (a, b) in swaps {
+                let output = line.rsplit_once(" -> ").map(|(_, output)| output);
+                if output == Some(*a) || output == Some(*b) {
+                    let other = if output == Some(*a) { b } else { a };
+                    *line = format!("{} -> {}", line.rsplit_once(" -> ").unwrap().0, other);
+                    break;
+                }
+            }
+        }
+
+        lines.join("\n")
+    }
+
+    #[test]
+    fn test_check_adder() {
+        let adder = ripple_carry_adder(45, &[]);
+        let device = MonitoringDevice::from_input(&adder).unwrap();
+        assert!(device.check_adder().unwrap().is_adder());
+
+        let swaps = [
+            ("p10", "g10"),
+            ("z15", "c15"),
+            ("t22", "z22"),
+            ("z30", "g30"),
+        ];
+        let broken = ripple_carry_adder(45, &swaps);
+        let device = MonitoringDevice::from_input(&broken).unwrap();
+        let report = device.check_adder().unwrap();
+        assert_eq!(
+            report.swapped_wires(),
+            ["c15", "g10", "g30", "p10", "t22", "z15", "z22", "z30"]
+        );
+        assert!(device.repairs_adder(&report));
+        let partial = AdderReport {
+            swaps: report.swaps[..3].to_vec(),
+            ..report.clone()
+        };
+        assert!(!device.repairs_adder(&partial));
+        assert_eq!(
+            report.faults[0],
+            AdderFault {
+                bit: 10,
+                gate: "x10 XOR y10 -> g10".to_string(),
+                expected: "p10".to_string(),
+            }
+        );
+        assert_eq!(
+            solution_part2(&broken, 4, SimulatedOperation::Sum).as_deref(),
+            Ok("c15,g10,g30,p10,t22,z15,z22,z30")
+        );
+
+        let not_an_adder = adder.replace("x03 AND y03", "x03 OR y03");
+        assert_eq!(
+            MonitoringDevice::from_input(&not_an_adder)
+                .unwrap()
+                .check_adder(),
+            Err(AdderError::MissingGate {
+                bit: 3,
+                gate: "x03 AND y03".to_string(),
+            })
+        );
+        // too many gates to search for swaps once the adder check fails
+        assert_eq!(
+            solution_part2(&not_an_adder, 4, SimulatedOperation::Sum),
+            Err(PuzzleError::Repair(RepairError::TooManyCandidates))
+        );
+
+        let input_as_output = "x00: 1\ny00: 0\nz00: 0\n\nx00 XOR y00 -> s00\nx00 AND y00 -> z01";
+        let error = MonitoringDevice::from_input(input_as_output)
+            .unwrap()
+            .check_adder()
+            .unwrap_err();
+        assert_eq!(error.to_string(), "bit 0: wire z00 has no driver");
     }
 
     #[test]
@@ -441,16 +1472,16 @@ x05 AND y05 -> z00";
         let mut pair = Pair::new();
         let len = 4;
         assert_eq!(pair, Pair(0, 1));
-        assert_eq!(pair.next(len), true);
+        assert!(pair.next(len));
         assert_eq!(pair, Pair(0, 2));
-        assert_eq!(pair.next(len), true);
+        assert!(pair.next(len));
         assert_eq!(pair, Pair(0, 3));
-        assert_eq!(pair.next(len), true);
+        assert!(pair.next(len));
         assert_eq!(pair, Pair(1, 2));
-        assert_eq!(pair.next(len), true);
+        assert!(pair.next(len));
         assert_eq!(pair, Pair(1, 3));
-        assert_eq!(pair.next(len), true);
+        assert!(pair.next(len));
         assert_eq!(pair, Pair(2, 3));
-        assert_eq!(pair.next(len), false);
+        assert!(!pair.next(len));
     }
 }
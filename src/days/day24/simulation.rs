@@ -0,0 +1,115 @@
+use super::{
+    BigUint, Circuit, CircuitError, Connection, MonitoringDevice, SimulatedOperation, WireId,
+};
+
+/// How many evaluations a word of values holds.
+pub const LANES: usize = u64::BITS as usize;
+
+impl MonitoringDevice<'_> {
+    /// `z` for each `x` and `y`, the other inputs keeping their initial values. Pairs are
+    /// evaluated [`LANES`] at a time.
+    pub fn simulate(&self, pairs: &[(BigUint, BigUint)]) -> Result<Vec<BigUint>, CircuitError> {
+        self.simulate_with(&self.connections, pairs)
+    }
+
+    pub(super) fn simulate_with(
+        &self,
+        connections: &[Connection<'_>],
+        pairs: &[(BigUint, BigUint)],
+    ) -> Result<Vec<BigUint>, CircuitError> {
+        let circuit = Circuit::new(self.initial_values.keys().copied(), connections)?;
+        let initial_values = circuit
+            .inputs()
+            .map(|wire| match self.initial_values[circuit.name(wire)] {
+                0 => 0,
+                _ => u64::MAX,
+            })
+            .collect::<Vec<_>>();
+        let [x, y, z] = ["x", "y", "z"].map(|prefix| circuit.wires_starting_with(prefix));
+
+        let mut outputs = Vec::with_capacity(pairs.len());
+        for chunk in pairs.chunks(LANES) {
+            let mut inputs = initial_values.clone();
+            for (lane, (x_value, y_value)) in chunk.iter().enumerate() {
+                write_lane(&mut inputs, &x, lane, x_value);
+                write_lane(&mut inputs, &y, lane, y_value);
+            }
+
+            let values = circuit.evaluate(&inputs);
+            outputs.extend((0..chunk.len()).map(|lane| read_lane(&values, &z, lane)));
+        }
+
+        Ok(outputs)
+    }
+
+    /// A pair among `samples` random `x` and `y` for which `z` isn't what `operation` gives, the
+    /// same `seed` giving the same pairs.
+    pub fn find_counterexample(
+        &self,
+        operation: &SimulatedOperation,
+        samples: usize,
+        seed: u64,
+    ) -> Result<Option<(BigUint, BigUint)>, CircuitError> {
+        let pairs = self.random_pairs(samples, seed);
+        let outputs = self.simulate(&pairs)?;
+        let z_width = self.z_width();
+
+        Ok(pairs
+            .into_iter()
+            .zip(outputs)
+            .find(|((x, y), z)| operation.apply(x, y, z_width) != *z)
+            .map(|(pair, _)| pair))
+    }
+
+    /// `samples` random `x` and `y` as wide as their wires, the same `seed` giving the same
+    /// pairs.
+    pub(super) fn random_pairs(&self, samples: usize, seed: u64) -> Vec<(BigUint, BigUint)> {
+        let bits = |prefix| {
+            self.initial_values
+                .keys()
+                .filter(|wire| wire.starts_with(prefix))
+                .count()
+        };
+        let (x_width, y_width) = (bits('x'), bits('y'));
+
+        let mut random = SplitMix64(seed);
+        let mut number = |width: usize| {
+            let limbs = (0..width.div_ceil(64)).map(|_| random.next()).collect();
+            BigUint::from_limbs(limbs).truncate(width)
+        };
+        (0..samples)
+            .map(|_| (number(x_width), number(y_width)))
+            .collect()
+    }
+}
+
+/// The number whose bits are the values of `wires` in `lane`, the first wire being the least
+/// significant bit.
+fn read_lane(values: &[u64], wires: &[WireId], lane: usize) -> BigUint {
+    BigUint::from_bits(
+        wires
+            .iter()
+            .map(|wire| values[wire.index()] >> lane & 1 == 1),
+    )
+}
+
+/// Sets `wires` to the bits of `number` in `lane`, the other lanes being left as they are.
+fn write_lane(values: &mut [u64], wires: &[WireId], lane: usize, number: &BigUint) {
+    for (bit, wire) in wires.iter().enumerate() {
+        let set = number.bit(bit) as u64;
+        values[wire.index()] = values[wire.index()] & !(1 << lane) | set << lane;
+    }
+}
+
+/// A small generator that's good enough to pick test vectors.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
@@ -0,0 +1,243 @@
+use std::fmt::Display;
+
+use crate::{ParseError, Source};
+
+use super::{Connection, Gate, MonitoringDevice};
+
+/// Structural Verilog for a [`MonitoringDevice`]: a module whose inputs are the wires with an
+/// initial value and whose outputs are the `z` wires, with a gate primitive per gate.
+pub struct Verilog<'d, 'a> {
+    device: &'d MonitoringDevice<'a>,
+}
+
+impl<'a> MonitoringDevice<'a> {
+    pub fn verilog(&self) -> Verilog<'_, 'a> {
+        Verilog { device: self }
+    }
+
+    /// Reads a module of `and`, `or`, `xor`, `nand`, `nor`, `xnor` and `not` primitives with two
+    /// inputs, one for `not`, like [`MonitoringDevice::verilog`] writes. Inputs start at 0.
+    pub fn from_verilog(input: &'a str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+        let mut tokens = Tokens {
+            source,
+            tokens: tokens(input).into_iter(),
+        };
+
+        tokens.expect("module")?;
+        tokens.name()?;
+        if tokens.next("`(` or `;`")? == "(" {
+            tokens.names(")")?;
+            tokens.expect(";")?;
+        }
+
+        let mut inputs = vec![];
+        let mut connections = vec![];
+        loop {
+            let token = tokens.next("`endmodule`")?;
+            match token {
+                "endmodule" => break,
+                "input" => inputs.extend(tokens.names(";")?),
+                "output" | "wire" => {
+                    tokens.names(";")?;
+                }
+                _ => {
+                    let gate = primitive(token).ok_or_else(|| {
+                        source.error(token, "a declaration, a gate or `endmodule`")
+                    })?;
+                    // the instance name is optional
+                    let mut token = tokens.next("`(`")?;
+                    if token != "(" {
+                        name(&source, token)?;
+                        token = tokens.next("`(`")?;
+                    }
+                    if token != "(" {
+                        return Err(source.error(token, "`(`"));
+                    }
+
+                    let ports = tokens.names(")")?;
+                    tokens.expect(";")?;
+                    let &[result, left, ref rest @ ..] = &ports[..] else {
+                        return Err(source.error(token, "an output and inputs"));
+                    };
+                    connections.push(match (gate, rest) {
+                        (Gate::Not, []) => Connection::not(left, result),
+                        (Gate::Not, _) => return Err(source.error(token, "a single input")),
+                        (_, &[right]) => Connection {
+                            left,
+                            right,
+                            gate,
+                            result,
+                        },
+                        _ => return Err(source.error(token, "two inputs")),
+                    });
+                }
+            }
+        }
+
+        let device = Self::from_netlist(inputs, connections);
+        device.check(&source)?;
+        Ok(device)
+    }
+}
+
+fn primitive(keyword: &str) -> Option<Gate> {
+    Gate::ALL
+        .into_iter()
+        .find(|gate| gate.to_string().to_lowercase() == keyword)
+}
+
+/// The identifiers, escaped ones keeping their backslash, and the punctuation of a module,
+/// without whitespace and comments.
+fn tokens(input: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    let mut rest = input;
+
+    while let Some(c) = rest.chars().next() {
+        let len = if c.is_whitespace() {
+            rest.find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len())
+        } else if rest.starts_with("//") {
+            rest.find('\n').unwrap_or(rest.len())
+        } else if rest.starts_with("/*") {
+            rest.find("*/").map_or(rest.len(), |end| end + 2)
+        } else {
+            let len = if c == '\\' {
+                rest.find(char::is_whitespace).unwrap_or(rest.len())
+            } else if is_identifier_char(c) {
+                rest.find(|c| !is_identifier_char(c)).unwrap_or(rest.len())
+            } else {
+                c.len_utf8()
+            };
+            tokens.push(&rest[..len]);
+            len
+        };
+        rest = &rest[len..];
+    }
+
+    tokens
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
+
+/// The wire a token stands for, without the backslash of an escaped identifier.
+fn name<'a>(source: &Source, token: &'a str) -> Result<&'a str, ParseError> {
+    match token.strip_prefix('\\') {
+        Some(name) => Ok(name),
+        None if token.chars().all(is_identifier_char) && !KEYWORDS.contains(&token) => Ok(token),
+        None => Err(source.error(token, "a wire name")),
+    }
+}
+
+struct Tokens<'a, I> {
+    source: Source<'a>,
+    tokens: I,
+}
+
+impl<'a, I: Iterator<Item = &'a str>> Tokens<'a, I> {
+    fn next(&mut self, expected: &str) -> Result<&'a str, ParseError> {
+        self.tokens.next().ok_or_else(|| self.source.end(expected))
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), ParseError> {
+        let token = self.next(&format!("`{}`", expected))?;
+        if token != expected {
+            return Err(self.source.error(token, format!("`{}`", expected)));
+        }
+        Ok(())
+    }
+
+    fn name(&mut self) -> Result<&'a str, ParseError> {
+        let token = self.next("a name")?;
+        name(&self.source, token)
+    }
+
+    /// Names separated by commas, up to `end`.
+    fn names(&mut self, end: &str) -> Result<Vec<&'a str>, ParseError> {
+        let mut names = vec![self.name()?];
+        loop {
+            let token = self.next(&format!("`,` or `{}`", end))?;
+            match token {
+                "," => names.push(self.name()?),
+                _ if token == end => return Ok(names),
+                _ => return Err(self.source.error(token, format!("`,` or `{}`", end))),
+            }
+        }
+    }
+}
+
+/// The words a wire can't be named after without escaping it.
+const KEYWORDS: &[&str] = &[
+    "module",
+    "endmodule",
+    "input",
+    "output",
+    "wire",
+    "assign",
+    "buf",
+    "and",
+    "or",
+    "xor",
+    "nand",
+    "nor",
+    "xnor",
+    "not",
+];
+
+struct Identifier<'a>(&'a str);
+
+impl Display for Identifier<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let simple = self
+            .0
+            .starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && self.0.chars().all(is_identifier_char)
+            && !KEYWORDS.contains(&self.0);
+        if simple {
+            write!(f, "{}", self.0)
+        } else {
+            write!(f, "\\{} ", self.0)
+        }
+    }
+}
+
+fn list(wires: &[&str]) -> String {
+    wires
+        .iter()
+        .map(|wire| Identifier(wire).to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Display for Verilog<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [inputs, outputs, wires] = self.device.ports();
+
+        let ports = inputs.iter().chain(&outputs).copied().collect::<Vec<_>>();
+        writeln!(f, "module circuit({});", list(&ports))?;
+        for (keyword, wires) in [("input", inputs), ("output", outputs), ("wire", wires)] {
+            if !wires.is_empty() {
+                writeln!(f, "    {} {};", keyword, list(&wires))?;
+            }
+        }
+
+        if !self.device.connections.is_empty() {
+            writeln!(f)?;
+        }
+        for connection in &self.device.connections {
+            let ports = std::iter::once(connection.result)
+                .chain(connection.inputs())
+                .collect::<Vec<_>>();
+            writeln!(
+                f,
+                "    {} ({});",
+                connection.gate.to_string().to_lowercase(),
+                list(&ports)
+            )?;
+        }
+
+        writeln!(f, "endmodule")
+    }
+}
@@ -1,38 +1,70 @@
 // https://adventofcode.com/2024/day/25
 
-pub fn solution_part1(input: &str) -> usize {
-    let tumbler_locks = TumblerLocks::from_input(input);
-    tumbler_locks.fitting_keys()
+use crate::{solution::Solution, ParseError, Source};
+
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let tumbler_locks = TumblerLocks::from_input(input)?;
+    Ok(tumbler_locks.fitting_keys())
 }
 
-pub fn solution_part2(_input: &str) -> usize {
-    0
+pub fn solution_part2(_input: &str) -> Result<usize, ParseError> {
+    Ok(0)
 }
 
-struct TumblerLocks {
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+
+    type Parsed<'a> = TumblerLocks;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<TumblerLocks, ParseError> {
+        TumblerLocks::from_input(input)
+    }
+
+    fn part1(tumbler_locks: TumblerLocks, _: &()) -> usize {
+        tumbler_locks.fitting_keys()
+    }
+
+    fn part2(_: TumblerLocks, _: &()) -> usize {
+        0
+    }
+}
+
+pub struct TumblerLocks {
     keys: Vec<[u8; 5]>,
     locks: Vec<[u8; 5]>,
 }
 
 impl TumblerLocks {
-    fn from_input(input: &str) -> Self {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
         let mut keys = vec![];
         let mut locks = vec![];
 
         for thing_str in input.split("\n\n") {
             let lines = thing_str.lines().collect::<Vec<_>>();
             let [first_line, other_lines @ .., _] = lines.as_slice() else {
-                unreachable!("input is predictable");
+                return Err(source.error(thing_str, "a lock or key schematic"));
             };
 
-            let thing = other_lines.iter().fold([0u8; 5], |mut acc, l| {
-                for (i, c) in l.chars().enumerate() {
-                    if c == '#' {
-                        acc[i] += 1;
+            let mut thing = [0u8; 5];
+            for l in other_lines {
+                if l.len() != 5 {
+                    return Err(source.error(l, "a line of 5 tiles"));
+                }
+
+                for (i, c) in l.char_indices() {
+                    match c {
+                        '#' => thing[i] += 1,
+                        '.' => {}
+                        _ => return Err(source.char_error(&l[i..], "`#` or `.`")),
                     }
                 }
-                acc
-            });
+            }
 
             if first_line == &"#####" {
                 locks.push(thing);
@@ -41,11 +73,10 @@ impl TumblerLocks {
             }
         }
 
-        Self { keys, locks }
+        Ok(Self { keys, locks })
     }
 
     fn fitting_keys(&self) -> usize {
-        println!("{:?}\n\n{:?}", self.keys, self.locks);
         self.keys
             .iter()
             .map(|key| {
@@ -67,6 +98,7 @@ impl TumblerLocks {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "#####
 .####
@@ -108,15 +140,18 @@ mod tests {
 #.#.#
 #####";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 3);
+        assert_eq!(solution_part1(EXAMPLE), Ok(3));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 3249);
+        let expected = real_answer!(25, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(25)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
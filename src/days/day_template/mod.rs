@@ -1,38 +1,90 @@
 // https://adventofcode.com/2024/day/1
 
-pub fn solution_part1(input: &str) -> usize {
-    0
+use crate::{solution::Solution, ParseError};
+
+pub fn solution_part1(input: &str) -> Result<usize, ParseError> {
+    let puzzle = Puzzle::from_input(input)?;
+    Ok(puzzle.part1())
+}
+
+pub fn solution_part2(input: &str) -> Result<usize, ParseError> {
+    let puzzle = Puzzle::from_input(input)?;
+    Ok(puzzle.part2())
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Parsed<'a> = Puzzle;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Puzzle::from_input(input)
+    }
+
+    fn part1(puzzle: Puzzle, _: &()) -> usize {
+        puzzle.part1()
+    }
+
+    fn part2(puzzle: Puzzle, _: &()) -> usize {
+        puzzle.part2()
+    }
 }
 
-pub fn solution_part2(input: &str) -> usize {
-    0
+pub struct Puzzle {}
+
+impl Puzzle {
+    fn from_input(_input: &str) -> Result<Self, ParseError> {
+        Ok(Self {})
+    }
+
+    fn part1(&self) -> usize {
+        0
+    }
+
+    fn part2(&self) -> usize {
+        0
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{answers::real_answer, input::real_input, solution::Part};
 
     const EXAMPLE: &str = "";
 
-    const INPUT: &str = include_str!("input.txt");
-
     #[test]
     fn test_part1_example() {
-        assert_eq!(solution_part1(EXAMPLE), 0);
+        assert_eq!(solution_part1(EXAMPLE), Ok(0));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part1() {
-        assert_eq!(solution_part1(INPUT), 0);
+        let expected = real_answer!(1, Part::One);
+        assert_eq!(
+            solution_part1(&real_input!(1)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 
     #[test]
     fn test_part2_example() {
-        assert_eq!(solution_part2(EXAMPLE), 0);
+        assert_eq!(solution_part2(EXAMPLE), Ok(0));
     }
 
     #[test]
+    #[ignore = "needs the real input, run with --ignored"]
     fn test_part2() {
-        assert_eq!(solution_part2(INPUT), 0);
+        let expected = real_answer!(1, Part::Two);
+        assert_eq!(
+            solution_part2(&real_input!(1)).map(|answer| answer.to_string()),
+            Ok(expected)
+        );
     }
 }
@@ -1,3 +1,5 @@
+use crate::solution::{erase, DynSolution, Part};
+
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -23,3 +25,65 @@ pub mod day22;
 pub mod day23;
 pub mod day24;
 pub mod day25;
+
+/// Parts that don't finish in a reasonable time on a real input, tools skip them unless asked.
+pub const TOO_SLOW: &[(u8, Part)] = &[];
+
+pub fn registry() -> Vec<Box<dyn DynSolution>> {
+    vec![
+        erase::<day01::Day01>(),
+        erase::<day02::Day02>(),
+        erase::<day03::Day03>(),
+        erase::<day04::Day04>(),
+        erase::<day05::Day05>(),
+        erase::<day06::Day06>(),
+        erase::<day07::Day07>(),
+        erase::<day08::Day08>(),
+        erase::<day09::Day09>(),
+        erase::<day10::Day10>(),
+        erase::<day11::Day11>(),
+        erase::<day12::Day12>(),
+        erase::<day13::Day13>(),
+        erase::<day14::Day14>(),
+        erase::<day15::Day15>(),
+        erase::<day16::Day16>(),
+        erase::<day17::Day17>(),
+        erase::<day18::Day18>(),
+        erase::<day19::Day19>(),
+        erase::<day20::Day20>(),
+        erase::<day21::Day21>(),
+        erase::<day22::Day22>(),
+        erase::<day23::Day23>(),
+        erase::<day24::Day24>(),
+        erase::<day25::Day25>(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::RunError;
+
+    #[test]
+    fn test_registry_has_every_day_in_order() {
+        let days = registry()
+            .iter()
+            .map(|solution| solution.day())
+            .collect::<Vec<_>>();
+
+        assert_eq!(days, (1..=25).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn test_registry_runs_with_params() {
+        let solution = &registry()[13];
+        let input = "p=2,4 v=2,-3";
+        let args = ["11".to_string(), "7".to_string()];
+
+        assert_eq!(solution.run(Part::One, input, &args), Ok("0".to_string()));
+        assert_eq!(
+            solution.run(Part::One, input, &["11".to_string(), "x".to_string()]),
+            Err(RunError::Args("invalid height: x".to_string()))
+        );
+    }
+}
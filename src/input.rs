@@ -0,0 +1,65 @@
+use std::{fmt::Display, path::PathBuf};
+
+/// Overrides the default `~/.cache/aoc/2024` directory the inputs are read from.
+pub const DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug)]
+pub enum InputError {
+    NoDirectory,
+    Missing(PathBuf),
+    Unreadable(PathBuf, std::io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NoDirectory => {
+                write!(f, "no input directory, set {} or HOME", DIR_VAR)
+            }
+            InputError::Missing(path) => write!(f, "no input at {}", path.display()),
+            InputError::Unreadable(path, error) => {
+                write!(f, "cannot read {}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn directory() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os(DIR_VAR).filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+
+    let home = std::env::var_os("HOME").filter(|home| !home.is_empty())?;
+    Some(PathBuf::from(home).join(".cache/aoc/2024"))
+}
+
+pub fn path(day: u8) -> Option<PathBuf> {
+    Some(directory()?.join(format!("day{:02}.txt", day)))
+}
+
+/// The input of `day`, without the trailing newline.
+pub fn load(day: u8) -> Result<String, InputError> {
+    let path = path(day).ok_or(InputError::NoDirectory)?;
+
+    match std::fs::read_to_string(&path) {
+        Ok(input) => Ok(input.trim_end().to_string()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            Err(InputError::Missing(path))
+        }
+        Err(error) => Err(InputError::Unreadable(path, error)),
+    }
+}
+
+/// Loads the real input of a day in a test. Those tests are ignored unless asked for with
+/// `--ignored`, as the input may well be missing, and then fail when it is.
+#[cfg(test)]
+macro_rules! real_input {
+    ($day:expr) => {
+        $crate::input::load($day).unwrap_or_else(|error| panic!("{}", error))
+    };
+}
+
+#[cfg(test)]
+pub(crate) use real_input;
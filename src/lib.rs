@@ -1,13 +1,38 @@
 #![feature(iter_array_chunks)]
 #![feature(slice_split_once)]
 
+use std::{
+    fmt::Display,
+    ops::{Add, Index, IndexMut, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+pub mod answers;
+pub mod bench;
+pub mod big;
 pub mod days;
+pub mod input;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Position(usize, usize);
 
 impl Position {
-    fn left(&self) -> Option<Position> {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self(x, y)
+    }
+
+    pub fn x(&self) -> usize {
+        self.0
+    }
+
+    pub fn y(&self) -> usize {
+        self.1
+    }
+
+    pub fn left(&self) -> Option<Position> {
         if self.0 > 0 {
             Some(Position(self.0 - 1, self.1))
         } else {
@@ -15,11 +40,11 @@ impl Position {
         }
     }
 
-    fn right(&self) -> Position {
+    pub fn right(&self) -> Position {
         Position(self.0 + 1, self.1)
     }
 
-    fn right_bounded(&self, width: usize) -> Option<Position> {
+    pub fn right_bounded(&self, width: usize) -> Option<Position> {
         if self.0 < width - 1 {
             Some(Position(self.0 + 1, self.1))
         } else {
@@ -27,7 +52,7 @@ impl Position {
         }
     }
 
-    fn up(&self) -> Option<Position> {
+    pub fn up(&self) -> Option<Position> {
         if self.1 > 0 {
             Some(Position(self.0, self.1 - 1))
         } else {
@@ -35,11 +60,11 @@ impl Position {
         }
     }
 
-    fn down(&self) -> Position {
+    pub fn down(&self) -> Position {
         Position(self.0, self.1 + 1)
     }
 
-    fn down_bounded(&self, height: usize) -> Option<Position> {
+    pub fn down_bounded(&self, height: usize) -> Option<Position> {
         if self.1 < height - 1 {
             Some(Position(self.0, self.1 + 1))
         } else {
@@ -47,7 +72,7 @@ impl Position {
         }
     }
 
-    fn move_by(&self, distance: &Distance, width: usize, height: usize) -> Option<Position> {
+    pub fn move_by(&self, distance: &Distance, width: usize, height: usize) -> Option<Position> {
         let target_x = self.0 as isize + distance.0;
         let target_y = self.1 as isize + distance.1;
 
@@ -59,18 +84,74 @@ impl Position {
         }
     }
 
-    fn move_by_wrapping(&self, distance: &Distance, width: usize, height: usize) -> Position {
+    pub fn move_by_wrapping(&self, distance: &Distance, width: usize, height: usize) -> Position {
         let target_x = (self.0 as isize + distance.0).rem_euclid(width as isize) as usize;
         let target_y = (self.1 as isize + distance.1).rem_euclid(height as isize) as usize;
 
         Position(target_x, target_y)
     }
+
+    pub fn manhattan_distance(&self, other: &Position) -> usize {
+        Distance::between(self, other).manhattan()
+    }
+
+    pub fn chebyshev_distance(&self, other: &Position) -> usize {
+        Distance::between(self, other).chebyshev()
+    }
+}
+
+/// `None` when the result would have a negative coordinate.
+impl Add<&Distance> for &Position {
+    type Output = Option<Position>;
+
+    fn add(self, distance: &Distance) -> Option<Position> {
+        Some(Position(
+            self.0.checked_add_signed(distance.0)?,
+            self.1.checked_add_signed(distance.1)?,
+        ))
+    }
+}
+
+impl Add<Distance> for Position {
+    type Output = Option<Position>;
+
+    fn add(self, distance: Distance) -> Option<Position> {
+        &self + &distance
+    }
+}
+
+impl Sub<&Position> for &Position {
+    type Output = Distance;
+
+    fn sub(self, other: &Position) -> Distance {
+        Distance::between(other, self)
+    }
+}
+
+impl Sub for Position {
+    type Output = Distance;
+
+    fn sub(self, other: Position) -> Distance {
+        &self - &other
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Distance(isize, isize);
 
 impl Distance {
+    pub fn new(dx: isize, dy: isize) -> Self {
+        Self(dx, dy)
+    }
+
+    pub fn dx(&self) -> isize {
+        self.0
+    }
+
+    pub fn dy(&self) -> isize {
+        self.1
+    }
+
     pub fn between(position1: &Position, position2: &Position) -> Self {
         Self(
             position2.0 as isize - position1.0 as isize,
@@ -82,45 +163,546 @@ impl Distance {
         Distance(self.0 * n as isize, self.1 * n as isize)
     }
 
+    pub fn manhattan(&self) -> usize {
+        self.0.unsigned_abs() + self.1.unsigned_abs()
+    }
+
+    pub fn chebyshev(&self) -> usize {
+        self.0.unsigned_abs().max(self.1.unsigned_abs())
+    }
+
     pub fn from_direction(direction: &Direction) -> Self {
         match direction {
             Direction::Up => Distance(0, -1),
+            Direction::UpRight => Distance(1, -1),
+            Direction::Right => Distance(1, 0),
+            Direction::DownRight => Distance(1, 1),
             Direction::Down => Distance(0, 1),
+            Direction::DownLeft => Distance(-1, 1),
             Direction::Left => Distance(-1, 0),
-            Direction::Right => Distance(1, 0),
+            Direction::UpLeft => Distance(-1, -1),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+impl From<Direction> for Distance {
+    fn from(direction: Direction) -> Self {
+        Distance::from_direction(&direction)
+    }
+}
+
+impl Add for Distance {
+    type Output = Distance;
+
+    fn add(self, other: Distance) -> Distance {
+        Distance(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+impl Sub for Distance {
+    type Output = Distance;
+
+    fn sub(self, other: Distance) -> Distance {
+        Distance(self.0 - other.0, self.1 - other.1)
+    }
+}
+
+impl Mul<isize> for Distance {
+    type Output = Distance;
+
+    fn mul(self, n: isize) -> Distance {
+        Distance(self.0 * n, self.1 * n)
+    }
+}
+
+impl Neg for Distance {
+    type Output = Distance;
+
+    fn neg(self) -> Distance {
+        Distance(-self.0, -self.1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Direction {
     Up,
+    UpRight,
+    Right,
+    DownRight,
     Down,
+    DownLeft,
     Left,
-    Right,
+    UpLeft,
 }
 
 impl Direction {
-    fn turn_left(&self) -> Self {
-        match self {
-            Direction::Up => Direction::Left,
-            Direction::Down => Direction::Right,
-            Direction::Left => Direction::Down,
-            Direction::Right => Direction::Up,
+    /// Clockwise, starting up.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Clockwise, starting up.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// Parses the `^`, `v`, `<` and `>` arrows.
+    pub fn from_arrow(arrow: char) -> Option<Self> {
+        match arrow {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        matches!(
+            self,
+            Direction::UpRight | Direction::DownRight | Direction::DownLeft | Direction::UpLeft
+        )
+    }
+
+    pub fn turn_left(&self) -> Self {
+        self.rotate(6)
+    }
+
+    pub fn turn_right(&self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.rotate(4)
+    }
+
+    /// By `eighths` of a full clockwise turn.
+    fn rotate(&self, eighths: usize) -> Self {
+        let index = Direction::ALL
+            .iter()
+            .position(|direction| direction == self)
+            .unwrap();
+
+        Direction::ALL[(index + eighths) % 8]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
         }
     }
 
-    fn turn_right(&self) -> Self {
-        match self {
-            Direction::Up => Direction::Right,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-            Direction::Right => Direction::Down,
+    /// `parse_cell` returns what was expected instead when it rejects a cell.
+    pub fn from_input(
+        source: &Source,
+        input: &str,
+        mut parse_cell: impl FnMut(char, Position) -> Result<T, &'static str>,
+    ) -> Result<Self, ParseError> {
+        let Some(width) = input
+            .lines()
+            .next()
+            .map(|line| line.chars().count())
+            .filter(|width| *width > 0)
+        else {
+            return Err(source.error(input, "a grid"));
+        };
+
+        let mut cells = vec![];
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            if line.chars().count() != width {
+                return Err(source.error(line, format!("a line of {} cells", width)));
+            }
+
+            for (x, (i, c)) in line.char_indices().enumerate() {
+                let cell = parse_cell(c, Position(x, y))
+                    .map_err(|expected| source.char_error(&line[i..], expected))?;
+                cells.push(cell);
+            }
+
+            height += 1;
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        position.0 < self.width && position.1 < self.height
+    }
+
+    pub fn get(&self, position: &Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.1 * self.width + position.0])
+    }
+
+    pub fn get_mut(&mut self, position: &Position) -> Option<&mut T> {
+        self.contains(position)
+            .then(|| &mut self.cells[position.1 * self.width + position.0])
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Position(i % width, i / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    pub fn neighbours(&self, position: &Position) -> impl Iterator<Item = Position> {
+        self.neighbours_by(position, &Direction::CARDINAL)
+    }
+
+    pub fn neighbours8(&self, position: &Position) -> impl Iterator<Item = Position> {
+        self.neighbours_by(position, &Direction::ALL)
+    }
+
+    fn neighbours_by(
+        &self,
+        position: &Position,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = Position> {
+        let (width, height) = (self.width, self.height);
+        let position = position.clone();
+
+        directions.iter().filter_map(move |direction| {
+            position.move_by(&Distance::from_direction(direction), width, height)
+        })
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = (Position, &T)>> {
+        (0..self.height).map(|y| self.line(Position(0, y), Distance(1, 0)))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = (Position, &T)>> {
+        (0..self.width).map(|x| self.line(Position(x, 0), Distance(0, 1)))
+    }
+
+    /// Top-left to bottom-right lines, starting from the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (Position, &T)>> {
+        let starts = (0..self.height)
+            .rev()
+            .map(|y| Position(0, y))
+            .chain((1..self.width).map(|x| Position(x, 0)));
+
+        starts.map(|start| self.line(start, Distance(1, 1)))
+    }
+
+    /// Top-right to bottom-left lines, starting from the top-left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (Position, &T)>> {
+        let last_x = self.width.saturating_sub(1);
+        let starts = (0..self.width)
+            .map(|x| Position(x, 0))
+            .chain((1..self.height).map(move |y| Position(last_x, y)));
+
+        starts.map(|start| self.line(start, Distance(-1, 1)))
+    }
+
+    fn line(&self, start: Position, step: Distance) -> impl Iterator<Item = (Position, &T)> {
+        std::iter::successors(Some(start).filter(|start| self.contains(start)), move |p| {
+            p.move_by(&step, self.width, self.height)
+        })
+        .map(|position| {
+            let cell = &self[&position];
+            (position, cell)
+        })
+    }
+}
+
+impl<T> Index<&Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: &Position) -> &T {
+        self.get(position).expect("position out of the grid")
+    }
+}
+
+impl<T> IndexMut<&Position> for Grid<T> {
+    fn index_mut(&mut self, position: &Position) -> &mut T {
+        self.get_mut(position).expect("position out of the grid")
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.cells.chunks(self.width.max(1)).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: Option<String>,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+
+        match &self.found {
+            Some(found) => write!(f, "`{}`", found),
+            None => write!(f, "nothing"),
         }
     }
 }
 
+impl std::error::Error for ParseError {}
+
+/// The whole puzzle input, used to turn any sub-slice of it into a [`ParseError`] that
+/// points at the right line and column.
+#[derive(Clone, Copy)]
+pub struct Source<'a>(&'a str);
+
+impl<'a> Source<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self(input)
+    }
+
+    /// `at` is expected to be a slice of the input, any other fragment, like an owned copy, is
+    /// reported at the end of the input.
+    pub fn error(&self, at: &str, expected: impl Into<String>) -> ParseError {
+        let input = self.0.as_bytes().as_ptr_range();
+        let offset = if input.contains(&at.as_ptr()) {
+            at.as_ptr() as usize - input.start as usize
+        } else {
+            self.0.len()
+        };
+        let before = &self.0[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let found = at
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().next())
+            .map(str::to_string);
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: expected.into(),
+            found,
+        }
+    }
+
+    pub fn char_error(&self, at: &str, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            found: at.chars().next().map(String::from),
+            ..self.error(at, expected)
+        }
+    }
+
+    pub fn end(&self, expected: impl Into<String>) -> ParseError {
+        self.error(&self.0[self.0.len()..], expected)
+    }
+
+    pub fn number<T: FromStr>(&self, fragment: &str) -> Result<T, ParseError> {
+        fragment
+            .parse::<T>()
+            .map_err(|_| self.error(fragment, "a number"))
+    }
+
+    pub fn split_once<'b>(
+        &self,
+        fragment: &'b str,
+        delimiter: &str,
+    ) -> Result<(&'b str, &'b str), ParseError> {
+        fragment
+            .split_once(delimiter)
+            .ok_or_else(|| self.error(fragment, format!("`{}`", delimiter)))
+    }
+
+    pub fn strip_prefix<'b>(&self, fragment: &'b str, prefix: &str) -> Result<&'b str, ParseError> {
+        fragment
+            .strip_prefix(prefix)
+            .ok_or_else(|| self.error(fragment, format!("`{}`", prefix)))
+    }
+
+    pub fn next_line<'b, I: Iterator<Item = &'b str>>(
+        &self,
+        lines: &mut I,
+        expected: &str,
+    ) -> Result<&'b str, ParseError> {
+        lines.next().ok_or_else(|| self.end(expected))
+    }
+}
+
 pub fn debug_pause() {
     let mut buf = String::new();
     std::io::stdin().read_line(&mut buf).unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRID: &str = "abc
+def";
+
+    fn grid() -> Grid<char> {
+        Grid::from_input(&Source::new(GRID), GRID, |c, _| Ok(c)).unwrap()
+    }
+
+    fn letters<'a>(
+        lines: impl Iterator<Item = impl Iterator<Item = (Position, &'a char)>>,
+    ) -> Vec<String> {
+        lines.map(|line| line.map(|(_, c)| *c).collect()).collect()
+    }
+
+    #[test]
+    fn test_position_arithmetic() {
+        let position = Position::new(2, 3);
+
+        assert_eq!(&position + &Distance::new(-2, 1), Some(Position::new(0, 4)));
+        assert_eq!(position.clone() + Distance::new(-3, 0), None);
+        assert_eq!(Position::new(5, 1) - position.clone(), Distance::new(3, -2));
+        assert_eq!(-Distance::new(1, -2) * 3, Distance::new(-3, 6));
+        assert_eq!(position.manhattan_distance(&Position::new(5, 1)), 5);
+        assert_eq!(position.chebyshev_distance(&Position::new(5, 1)), 3);
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Direction::from_arrow('v'), Some(Direction::Down));
+        assert_eq!(Direction::from_arrow('x'), None);
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::UpLeft.turn_left(), Direction::DownLeft);
+        assert_eq!(Direction::DownRight.opposite(), Direction::UpLeft);
+        assert_eq!(Direction::ALL.iter().filter(|d| d.is_diagonal()).count(), 4);
+        assert_eq!(
+            Direction::CARDINAL
+                .into_iter()
+                .map(Distance::from)
+                .fold(Distance::new(0, 0), |sum, d| sum + d),
+            Distance::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn test_source_error() {
+        let input = "ab\ncdé fg";
+        let source = Source::new(input);
+        let error = source.error(&input[8..], "a letter");
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.found.as_deref(), Some("fg"));
+
+        // a copy isn't a slice of the input, even when it is equal to one
+        let copy = input[4..].to_string();
+        let error = source.error(&copy, "a letter");
+        assert_eq!((error.line, error.column), (2, 7));
+        assert_eq!(error.found.as_deref(), Some("dé"));
+    }
+
+    #[test]
+    fn test_grid_from_input() {
+        let grid = grid();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(&Position(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(&Position(3, 0)), None);
+        assert_eq!(grid.to_string(), GRID);
+    }
+
+    #[test]
+    fn test_grid_from_input_errors() {
+        let source = Source::new("ab\nc");
+        assert_eq!(
+            Grid::from_input(&source, "ab\nc", |c, _| Ok(c)).map(|_| ()),
+            Err(source.error(&"ab\nc"[3..], "a line of 2 cells"))
+        );
+
+        let source = Source::new("a.\n..");
+        assert_eq!(
+            Grid::from_input(&source, "a.\n..", |c, _| match c {
+                '.' => Ok(()),
+                _ => Err("`.`"),
+            }),
+            Err(ParseError {
+                line: 1,
+                column: 1,
+                expected: "`.`".to_string(),
+                found: Some("a".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_grid_neighbours() {
+        let grid = grid();
+
+        assert_eq!(
+            grid.neighbours(&Position(0, 0)).collect::<Vec<_>>(),
+            vec![Position(1, 0), Position(0, 1)]
+        );
+        assert_eq!(grid.neighbours8(&Position(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn test_grid_lines() {
+        let grid = grid();
+
+        assert_eq!(letters(grid.rows()), ["abc", "def"]);
+        assert_eq!(letters(grid.columns()), ["ad", "be", "cf"]);
+        assert_eq!(letters(grid.diagonals()), ["d", "ae", "bf", "c"]);
+        assert_eq!(letters(grid.anti_diagonals()), ["a", "bd", "ce", "f"]);
+    }
+}
@@ -0,0 +1,231 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::input;
+
+const TEMPLATE: &str = "src/days/day_template/mod.rs";
+const DAYS: &str = "src/days/mod.rs";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    DayExists(u8),
+    Template(&'static str),
+    NoInputDirectory,
+    Io(PathBuf, std::io::Error),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaffoldError::DayExists(day) => write!(f, "day {} already exists", day),
+            ScaffoldError::Template(missing) => {
+                write!(f, "cannot find {} to fill in", missing)
+            }
+            ScaffoldError::NoInputDirectory => {
+                write!(f, "no input directory, set {} or HOME", input::DIR_VAR)
+            }
+            ScaffoldError::Io(path, error) => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+/// What [`generate`] created, for the caller to report.
+pub struct Generated {
+    pub module: PathBuf,
+    pub input: Option<PathBuf>,
+}
+
+/// Creates `src/days/dayNN/mod.rs` in the crate at `root` from the template, registers it in
+/// `src/days/mod.rs`, and creates an empty input file unless there is one already.
+pub fn generate(root: &Path, day: u8, example: Option<&str>) -> Result<Generated, ScaffoldError> {
+    let read = |path: &Path| {
+        std::fs::read_to_string(path).map_err(|error| ScaffoldError::Io(path.to_path_buf(), error))
+    };
+    let write = |path: &Path, contents: &str| {
+        std::fs::write(path, contents).map_err(|error| ScaffoldError::Io(path.to_path_buf(), error))
+    };
+
+    let directory = root.join(format!("src/days/day{:02}", day));
+    if directory.exists() {
+        return Err(ScaffoldError::DayExists(day));
+    }
+
+    // everything is checked before anything is written, and what was written is undone when a
+    // later step fails, so that a failure leaves no half day
+    let days_path = root.join(DAYS);
+    let original_days = read(&days_path)?;
+    let module = render(&read(&root.join(TEMPLATE))?, day, example)?;
+    let days = register(&original_days, day)?;
+    let input = input::path(day).ok_or(ScaffoldError::NoInputDirectory)?;
+
+    std::fs::create_dir(&directory).map_err(|error| ScaffoldError::Io(directory.clone(), error))?;
+    let module_path = directory.join("mod.rs");
+    let created = (|| {
+        write(&module_path, &module)?;
+        write(&days_path, &days)?;
+
+        if input.exists() {
+            return Ok(None);
+        }
+        if let Some(parent) = input.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|error| ScaffoldError::Io(parent.to_path_buf(), error))?;
+        }
+        write(&input, "")?;
+        Ok(Some(input))
+    })();
+
+    match created {
+        Ok(input) => Ok(Generated {
+            module: module_path,
+            input,
+        }),
+        Err(error) => {
+            // the first error is the one worth reporting, undoing is only a best effort
+            let _ = std::fs::remove_dir_all(&directory);
+            let _ = write(&days_path, &original_days);
+            Err(error)
+        }
+    }
+}
+
+/// The template, with its day 1 placeholders replaced by `day`.
+pub fn render(template: &str, day: u8, example: Option<&str>) -> Result<String, ScaffoldError> {
+    let replacements = [
+        ("day/1\n", format!("day/{}\n", day)),
+        ("Day01", format!("Day{:02}", day)),
+        ("const DAY: u8 = 1;", format!("const DAY: u8 = {};", day)),
+        ("real_input!(1)", format!("real_input!({})", day)),
+        ("real_answer!(1,", format!("real_answer!({},", day)),
+        (
+            "const EXAMPLE: &str = \"\";",
+            format!(
+                "const EXAMPLE: &str = \"{}\";",
+                example.map_or(String::new(), |example| escape(example.trim_end()))
+            ),
+        ),
+    ];
+
+    replacements
+        .into_iter()
+        .try_fold(template.to_string(), |module, (placeholder, value)| {
+            if !module.contains(placeholder) {
+                return Err(ScaffoldError::Template(placeholder.trim_end()));
+            }
+            Ok(module.replace(placeholder, &value))
+        })
+}
+
+fn escape(example: &str) -> String {
+    example.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// `days` with `day` added to the module declarations and to the registry, both kept sorted.
+pub fn register(days: &str, day: u8) -> Result<String, ScaffoldError> {
+    let mut lines = days.lines().map(str::to_string).collect::<Vec<_>>();
+
+    insert_sorted(
+        &mut lines,
+        format!("pub mod day{:02};", day),
+        "pub mod day",
+        day,
+    )?;
+    insert_sorted(
+        &mut lines,
+        format!("erase::<day{:02}::Day{:02}>(),", day, day),
+        "erase::<day",
+        day,
+    )?;
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn insert_sorted(
+    lines: &mut Vec<String>,
+    line: String,
+    prefix: &'static str,
+    day: u8,
+) -> Result<(), ScaffoldError> {
+    let similar = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, existing)| existing.trim_start().starts_with(prefix))
+        .map(|(i, existing)| (i, existing.trim_start()))
+        .collect::<Vec<_>>();
+
+    let &(first, _) = similar.first().ok_or(ScaffoldError::Template(prefix))?;
+    if similar.iter().any(|(_, existing)| *existing == line) {
+        return Err(ScaffoldError::DayExists(day));
+    }
+
+    let index = similar
+        .iter()
+        .find(|(_, existing)| *existing > line.as_str())
+        .map_or(similar.last().unwrap().0 + 1, |(i, _)| *i);
+    let indent = &lines[first][..lines[first].len() - similar[0].1.len()];
+
+    lines.insert(index, format!("{}{}", indent, line));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAYS: &str = "use crate::solution::{erase, DynSolution};
+
+pub mod day01;
+pub mod day03;
+
+pub fn registry() -> Vec<Box<dyn DynSolution>> {
+    vec![
+        erase::<day01::Day01>(),
+        erase::<day03::Day03>(),
+    ]
+}
+";
+
+    #[test]
+    fn test_render() {
+        let template = include_str!("days/day_template/mod.rs");
+        let module = render(template, 7, Some("say \"hi\"\n\\o/\n")).unwrap();
+
+        assert!(module.starts_with("// https://adventofcode.com/2024/day/7\n"));
+        assert!(module.contains("pub struct Day07;"));
+        assert!(module.contains("const DAY: u8 = 7;"));
+        assert!(module.contains("real_input!(7)"));
+        assert!(module.contains("real_answer!(7, Part::Two)"));
+        assert!(module.contains("const EXAMPLE: &str = \"say \\\"hi\\\"\n\\\\o/\";"));
+        assert!(!module.contains("Day01"));
+
+        assert!(matches!(
+            render("", 7, None),
+            Err(ScaffoldError::Template("day/1"))
+        ));
+    }
+
+    #[test]
+    fn test_register() {
+        let days = register(DAYS, 2).unwrap();
+
+        assert!(days.contains("pub mod day01;\npub mod day02;\npub mod day03;\n"));
+        assert!(days.contains(
+            "        erase::<day01::Day01>(),
+        erase::<day02::Day02>(),
+        erase::<day03::Day03>(),"
+        ));
+
+        let days = register(DAYS, 25).unwrap();
+        assert!(days.contains("pub mod day03;\npub mod day25;\n"));
+        assert!(days.contains("erase::<day25::Day25>(),\n    ]"));
+
+        assert!(matches!(
+            register(DAYS, 3),
+            Err(ScaffoldError::DayExists(3))
+        ));
+    }
+}
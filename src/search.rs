@@ -0,0 +1,236 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    hash::Hash,
+};
+
+/// What a search learnt about the nodes it reached: their distance to the closest start and
+/// every predecessor they have on a shortest path.
+pub struct SearchResult<N> {
+    distances: HashMap<N, usize>,
+    predecessors: HashMap<N, Vec<N>>,
+    goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash> SearchResult<N> {
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    pub fn distance(&self, node: &N) -> Option<usize> {
+        self.distances.get(node).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<N, usize> {
+        &self.distances
+    }
+
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// One shortest path, from a start to `node` included.
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        self.distances.get(node)?;
+
+        let mut path = vec![node.clone()];
+        while let Some(predecessor) = self.predecessors(path.last().unwrap()).first() {
+            path.push(predecessor.clone());
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Every shortest path, from a start to `node` included.
+    pub fn all_paths(&self, node: &N) -> Vec<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return vec![];
+        }
+
+        let predecessors = self.predecessors(node);
+        if predecessors.is_empty() {
+            return vec![vec![node.clone()]];
+        }
+
+        predecessors
+            .iter()
+            .flat_map(|predecessor| self.all_paths(predecessor))
+            .map(|mut path| {
+                path.push(node.clone());
+                path
+            })
+            .collect()
+    }
+
+    /// Every node that is on at least one shortest path to `node`, `node` included.
+    pub fn nodes_on_paths(&self, node: &N) -> HashSet<N> {
+        let mut nodes = HashSet::new();
+        if !self.distances.contains_key(node) {
+            return nodes;
+        }
+
+        let mut to_visit = vec![node.clone()];
+        while let Some(node) = to_visit.pop() {
+            if nodes.insert(node.clone()) {
+                to_visit.extend(self.predecessors(&node).iter().cloned());
+            }
+        }
+
+        nodes
+    }
+}
+
+/// Stops once `is_goal` matches and every other path as short as the one to that goal is known,
+/// or once every reachable node is explored.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    astar(
+        [start],
+        |node| neighbours(node).into_iter().map(|next| (next, 1)),
+        |_| 0,
+        is_goal,
+    )
+}
+
+pub fn dijkstra<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    astar(starts, neighbours, |_| 0, is_goal)
+}
+
+/// `heuristic` must never overestimate the distance left to a goal, and never drop by more than
+/// the cost of an edge, or the distances found aren't the shortest ones.
+pub fn astar<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> usize,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    let mut distances = HashMap::new();
+    let mut predecessors: HashMap<N, Vec<N>> = HashMap::new();
+    let mut explored = HashSet::new();
+    let mut goal = None;
+
+    let mut to_explore = BTreeMap::<usize, Vec<N>>::new();
+    for start in starts {
+        let estimate = heuristic(&start);
+        distances.insert(start.clone(), 0);
+        to_explore.entry(estimate).or_default().push(start);
+    }
+
+    while let Some((estimate, nodes)) = to_explore.pop_first() {
+        if goal.as_ref().is_some_and(|goal| estimate > distances[goal]) {
+            break;
+        }
+
+        for node in nodes {
+            if !explored.insert(node.clone()) {
+                continue;
+            }
+
+            if goal.is_none() && is_goal(&node) {
+                goal = Some(node);
+                continue;
+            }
+
+            let distance = distances[&node];
+            for (next, cost) in neighbours(&node) {
+                let next_distance = distance + cost;
+
+                match distances.get(&next) {
+                    Some(&known) if known < next_distance => continue,
+                    Some(&known) if known == next_distance => {
+                        predecessors.entry(next).or_default().push(node.clone());
+                        continue;
+                    }
+                    _ => {}
+                }
+
+                distances.insert(next.clone(), next_distance);
+                predecessors.insert(next.clone(), vec![node.clone()]);
+                to_explore
+                    .entry(next_distance + heuristic(&next))
+                    .or_default()
+                    .push(next);
+            }
+        }
+    }
+
+    SearchResult {
+        distances,
+        predecessors,
+        goal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 3 -> 4
+    //  \-> 2 -/
+    //  \-----(5)-----/
+    fn graph(node: &usize) -> Vec<(usize, usize)> {
+        match node {
+            0 => vec![(1, 1), (2, 1), (4, 5)],
+            1 | 2 => vec![(3, 1)],
+            3 => vec![(4, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let result = bfs(
+            0,
+            |node| graph(node).into_iter().map(|(next, _)| next),
+            |_| false,
+        );
+
+        assert_eq!(result.goal(), None);
+        assert_eq!(result.distance(&4), Some(1));
+        assert_eq!(result.distance(&3), Some(2));
+        assert_eq!(result.path(&4), Some(vec![0, 4]));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let result = dijkstra([0], graph, |node| *node == 4);
+
+        assert_eq!(result.goal(), Some(&4));
+        assert_eq!(result.distance(&4), Some(3));
+        assert_eq!(result.path(&4), Some(vec![0, 1, 3, 4]));
+        assert_eq!(result.predecessors(&3), &[1, 2]);
+        assert_eq!(
+            result.all_paths(&4),
+            vec![vec![0, 1, 3, 4], vec![0, 2, 3, 4]]
+        );
+        assert_eq!(result.nodes_on_paths(&4), HashSet::from([0, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn test_astar() {
+        let result = astar([0], graph, |node| (4 - node.min(&4)) / 2, |node| *node == 4);
+
+        assert_eq!(result.distance(&4), Some(3));
+        assert_eq!(result.path(&5), None);
+        assert!(result.all_paths(&5).is_empty());
+    }
+}
@@ -0,0 +1,195 @@
+use std::{
+    fmt::Display,
+    hint::black_box,
+    marker::PhantomData,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::ParseError;
+
+/// A day of the calendar, split into parsing and the two parts so that tools can run
+/// (and time) each step on its own.
+pub trait Solution {
+    const DAY: u8;
+
+    type Parsed<'a>;
+    type Params: Params;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError>;
+
+    fn part1(parsed: Self::Parsed<'_>, params: &Self::Params) -> Self::Answer1;
+
+    fn part2(parsed: Self::Parsed<'_>, params: &Self::Params) -> Self::Answer2;
+}
+
+/// Extra arguments a day needs on top of the input, the defaults are the values used by
+/// the real puzzle.
+pub trait Params: Default {
+    fn from_args(args: &mut Args) -> Result<Self, String>;
+}
+
+impl Params for () {
+    fn from_args(_args: &mut Args) -> Result<Self, String> {
+        Ok(())
+    }
+}
+
+pub struct Args<'a> {
+    args: &'a [String],
+    next: usize,
+}
+
+impl<'a> Args<'a> {
+    pub fn new(args: &'a [String]) -> Self {
+        Self { args, next: 0 }
+    }
+
+    pub fn next_or<T: FromStr>(&mut self, name: &str, default: T) -> Result<T, String> {
+        let Some(arg) = self.args.get(self.next) else {
+            return Ok(default);
+        };
+
+        self.next += 1;
+        arg.parse::<T>()
+            .map_err(|_| format!("invalid {}: {}", name, arg))
+    }
+
+    fn finish(self) -> Result<(), String> {
+        match self.args.get(self.next) {
+            Some(arg) => Err(format!("unexpected argument: {}", arg)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("part must be 1 or 2, got {}", s)),
+        }
+    }
+}
+
+/// Type-erased [`Solution`], what [`crate::days::registry`] lists.
+pub trait DynSolution {
+    fn day(&self) -> u8;
+
+    fn run(&self, part: Part, input: &str, args: &[String]) -> Result<String, RunError>;
+
+    /// Times a single step with the default params, the parts don't count the parsing.
+    fn time(&self, step: Step, input: &str) -> Result<Duration, ParseError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part(Part),
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Step::Parse => "parse",
+            Step::Part(Part::One) => "part1",
+            Step::Part(Part::Two) => "part2",
+        })
+    }
+}
+
+impl FromStr for Step {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Step::Parse),
+            "part1" => Ok(Step::Part(Part::One)),
+            "part2" => Ok(Step::Part(Part::Two)),
+            _ => Err(format!("step must be parse, part1 or part2, got {}", s)),
+        }
+    }
+}
+
+/// The answer of a part that can fail once the input is parsed, showing the error in its place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome<T, E>(pub Result<T, E>);
+
+impl<T: Display, E: Display> Display for Outcome<T, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Ok(answer) => write!(f, "{}", answer),
+            Err(error) => write!(f, "no answer: {}", error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunError {
+    Args(String),
+    Parse(ParseError),
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunError::Args(error) => write!(f, "{}", error),
+            RunError::Parse(error) => write!(f, "invalid input at {}", error),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+struct Erased<S>(PhantomData<S>);
+
+impl<S: Solution> DynSolution for Erased<S> {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn run(&self, part: Part, input: &str, args: &[String]) -> Result<String, RunError> {
+        let mut args = Args::new(args);
+        let params = S::Params::from_args(&mut args).map_err(RunError::Args)?;
+        args.finish().map_err(RunError::Args)?;
+
+        let parsed = S::parse(input).map_err(RunError::Parse)?;
+
+        Ok(match part {
+            Part::One => S::part1(parsed, &params).to_string(),
+            Part::Two => S::part2(parsed, &params).to_string(),
+        })
+    }
+
+    fn time(&self, step: Step, input: &str) -> Result<Duration, ParseError> {
+        let params = S::Params::default();
+
+        let start = Instant::now();
+        let parsed = black_box(S::parse(black_box(input))?);
+        let parse_time = start.elapsed();
+
+        let start = Instant::now();
+        match step {
+            Step::Parse => return Ok(parse_time),
+            Step::Part(Part::One) => drop(black_box(S::part1(parsed, &params))),
+            Step::Part(Part::Two) => drop(black_box(S::part2(parsed, &params))),
+        }
+
+        Ok(start.elapsed())
+    }
+}
+
+pub fn erase<S: Solution + 'static>() -> Box<dyn DynSolution> {
+    Box::new(Erased::<S>(PhantomData))
+}